@@ -0,0 +1,259 @@
+use std::io::{Read, Write};
+
+use types::packet::ProtocolVersion;
+
+use crate::{
+    packets::{Decodable, Encodable, VersionedDecodable, VersionedEncodable},
+    text::TextComponent,
+    VarInt,
+};
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum BossBarColor {
+    Pink = 0,
+    Blue = 1,
+    Red = 2,
+    Green = 3,
+    Yellow = 4,
+    Purple = 5,
+    White = 6,
+}
+
+impl Decodable for BossBarColor {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let color = VarInt::decode(reader)?;
+
+        Ok(match color.0 {
+            0 => Self::Pink,
+            1 => Self::Blue,
+            2 => Self::Red,
+            3 => Self::Green,
+            4 => Self::Yellow,
+            5 => Self::Purple,
+            6 => Self::White,
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid boss bar color, {}", color.0),
+            ))?,
+        })
+    }
+}
+
+impl Encodable for BossBarColor {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        VarInt(*self as i32).encode(writer)
+    }
+}
+
+// the number of notches the bar is split into.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum BossBarDivision {
+    None = 0,
+    Six = 1,
+    Ten = 2,
+    Twelve = 3,
+    Twenty = 4,
+}
+
+impl Decodable for BossBarDivision {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let division = VarInt::decode(reader)?;
+
+        Ok(match division.0 {
+            0 => Self::None,
+            1 => Self::Six,
+            2 => Self::Ten,
+            3 => Self::Twelve,
+            4 => Self::Twenty,
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid boss bar division, {}", division.0),
+            ))?,
+        })
+    }
+}
+
+impl Encodable for BossBarDivision {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        VarInt(*self as i32).encode(writer)
+    }
+}
+
+// sent as a bit field, the music is the one of the ender dragon fight.
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub struct BossBarFlags {
+    pub darken_sky: bool,
+    pub play_music: bool,
+    pub create_fog: bool,
+}
+
+impl Decodable for BossBarFlags {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let flags = u8::decode(reader)?;
+
+        Ok(BossBarFlags {
+            darken_sky: flags & 0x01 != 0,
+            play_music: flags & 0x02 != 0,
+            create_fog: flags & 0x04 != 0,
+        })
+    }
+}
+
+impl Encodable for BossBarFlags {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        let flags =
+            self.darken_sky as u8 | (self.play_music as u8) << 1 | (self.create_fog as u8) << 2;
+
+        flags.encode(writer)
+    }
+}
+
+// the health goes from 0 to 1.
+#[derive(PartialEq, Clone, Debug)]
+pub enum BossBarAction {
+    Add {
+        title: TextComponent,
+        health: f32,
+        color: BossBarColor,
+        division: BossBarDivision,
+        flags: BossBarFlags,
+    },
+    Remove,
+    UpdateHealth(f32),
+    UpdateTitle(TextComponent),
+    UpdateStyle {
+        color: BossBarColor,
+        division: BossBarDivision,
+    },
+    UpdateFlags(BossBarFlags),
+}
+
+impl VersionedDecodable for BossBarAction {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        let action = VarInt::decode(reader)?;
+
+        Ok(match action.0 {
+            0 => Self::Add {
+                title: TextComponent::decode_versioned(reader, version)?,
+                health: f32::decode(reader)?,
+                color: BossBarColor::decode(reader)?,
+                division: BossBarDivision::decode(reader)?,
+                flags: BossBarFlags::decode(reader)?,
+            },
+            1 => Self::Remove,
+            2 => Self::UpdateHealth(f32::decode(reader)?),
+            3 => Self::UpdateTitle(TextComponent::decode_versioned(reader, version)?),
+            4 => Self::UpdateStyle {
+                color: BossBarColor::decode(reader)?,
+                division: BossBarDivision::decode(reader)?,
+            },
+            5 => Self::UpdateFlags(BossBarFlags::decode(reader)?),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid boss bar action, {}", action.0),
+            ))?,
+        })
+    }
+}
+
+impl VersionedEncodable for BossBarAction {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        match self {
+            Self::Add {
+                title,
+                health,
+                color,
+                division,
+                flags,
+            } => {
+                VarInt(0).encode(writer)?;
+                title.encode_versioned(writer, version)?;
+                health.encode(writer)?;
+                color.encode(writer)?;
+                division.encode(writer)?;
+                flags.encode(writer)
+            }
+            Self::Remove => VarInt(1).encode(writer),
+            Self::UpdateHealth(health) => {
+                VarInt(2).encode(writer)?;
+                health.encode(writer)
+            }
+            Self::UpdateTitle(title) => {
+                VarInt(3).encode(writer)?;
+                title.encode_versioned(writer, version)
+            }
+            Self::UpdateStyle { color, division } => {
+                VarInt(4).encode(writer)?;
+                color.encode(writer)?;
+                division.encode(writer)
+            }
+            Self::UpdateFlags(flags) => {
+                VarInt(5).encode(writer)?;
+                flags.encode(writer)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_action_round_trips() {
+        let flags = BossBarFlags {
+            darken_sky: true,
+            play_music: false,
+            create_fog: true,
+        };
+        let actions = [
+            BossBarAction::Add {
+                title: TextComponent::text("Ender Dragon"),
+                health: 1.0,
+                color: BossBarColor::Purple,
+                division: BossBarDivision::Twelve,
+                flags,
+            },
+            BossBarAction::Remove,
+            BossBarAction::UpdateHealth(0.5),
+            BossBarAction::UpdateTitle(TextComponent::text("Wither")),
+            BossBarAction::UpdateStyle {
+                color: BossBarColor::White,
+                division: BossBarDivision::None,
+            },
+            BossBarAction::UpdateFlags(flags),
+        ];
+
+        // the titles are JSON until 1.20.3 and NBT since.
+        for version in [ProtocolVersion::V1_19_3, ProtocolVersion::V1_21] {
+            for (id, action) in actions.iter().enumerate() {
+                let mut buf = Vec::new();
+                action.encode_versioned(&mut buf, version).unwrap();
+                assert_eq!(buf[0] as usize, id);
+
+                let mut reader = &buf[..];
+                assert_eq!(
+                    &BossBarAction::decode_versioned(&mut reader, version).unwrap(),
+                    action
+                );
+                assert!(reader.is_empty());
+            }
+        }
+
+        let mut buf = Vec::new();
+        BossBarAction::UpdateFlags(flags)
+            .encode_versioned(&mut buf, ProtocolVersion::V1_21)
+            .unwrap();
+        assert_eq!(buf, [5, 0b101]);
+
+        assert!(BossBarAction::decode_versioned(&mut &[6u8][..], ProtocolVersion::V1_21).is_err());
+        assert!(BossBarColor::decode(&mut &[7u8][..]).is_err());
+    }
+}
@@ -0,0 +1,232 @@
+use proc_macros::MinecraftPacket;
+use types::packet::ProtocolVersion;
+use uuid::Uuid;
+
+use crate::{
+    datatypes::{bitset::BitSet, sized::SizedVec},
+    identifier::RegistryReference,
+    nbt,
+    packets::{Decodable, Encodable, VersionedDecodable, VersionedEncodable},
+    VarInt,
+};
+
+// the key a player signs their messages with, signed by Mojang.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct PublicKey {
+    pub expires_at: i64,
+    pub key: Vec<u8>,
+    pub key_signature: Vec<u8>,
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct ChatSession {
+    pub session_id: Uuid,
+    pub public_key: PublicKey,
+}
+
+pub type MessageSignature = SizedVec<u8, 256>;
+
+// which of the last 20 messages seen by the client were acknowledged, one bit per message.
+pub type AcknowledgedMessages = SizedVec<u8, 3>;
+
+// a message that came before a signed one, either as the id of a signature the client has cached or the full
+// signature. on the wire the id is offset by one, 0 means the signature follows.
+#[derive(Debug, PartialEq, Clone)]
+pub enum PreviousMessage {
+    Id(i32),
+    Signature(MessageSignature),
+}
+
+impl Decodable for PreviousMessage {
+    fn decode<R: std::io::Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let id = VarInt::decode(reader)?.0;
+
+        if id == 0 {
+            return Ok(Self::Signature(MessageSignature::decode(reader)?));
+        }
+
+        Ok(Self::Id(id - 1))
+    }
+}
+
+impl Encodable for PreviousMessage {
+    fn encode<W: std::io::Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        match self {
+            Self::Id(id) => VarInt(id + 1).encode(writer),
+            Self::Signature(signature) => {
+                VarInt(0).encode(writer)?;
+                signature.encode(writer)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum FilterType {
+    PassThrough,
+    FullyFiltered,
+    // the set bits are the characters that were filtered.
+    PartiallyFiltered(BitSet),
+}
+
+impl Decodable for FilterType {
+    fn decode<R: std::io::Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let filter_type = VarInt::decode(reader)?;
+
+        Ok(match filter_type.0 {
+            0 => Self::PassThrough,
+            1 => Self::FullyFiltered,
+            2 => Self::PartiallyFiltered(BitSet::decode(reader)?),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid filter type, {}", filter_type.0),
+            ))?,
+        })
+    }
+}
+
+impl Encodable for FilterType {
+    fn encode<W: std::io::Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        match self {
+            Self::PassThrough => VarInt(0).encode(writer),
+            Self::FullyFiltered => VarInt(1).encode(writer),
+            Self::PartiallyFiltered(mask) => {
+                VarInt(2).encode(writer)?;
+                mask.encode(writer)
+            }
+        }
+    }
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct ArgumentSignature {
+    pub argument_name: String,
+    pub signature: MessageSignature,
+}
+
+// how a message is shown, the parameters are 0 for the sender, 1 for the target and 2 for the content.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct ChatTypeDecoration {
+    pub translation_key: String,
+    pub parameters: Vec<VarInt>,
+    pub style: nbt::Value,
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct ChatType {
+    pub chat: ChatTypeDecoration,
+    pub narration: ChatTypeDecoration,
+}
+
+// an entry of the chat type registry. since 1.20.5 the server can define a chat type inline instead, before that
+// it is always an id and isn't offset.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ChatTypeReference(pub RegistryReference<ChatType>);
+
+impl VersionedDecodable for ChatTypeReference {
+    fn decode_versioned<R: std::io::Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        if version < ProtocolVersion::V1_20_5 {
+            return Ok(Self(RegistryReference::Id(VarInt::decode(reader)?)));
+        }
+
+        Ok(Self(RegistryReference::decode(reader)?))
+    }
+}
+
+impl VersionedEncodable for ChatTypeReference {
+    fn encode_versioned<W: std::io::Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        match (&self.0, version < ProtocolVersion::V1_20_5) {
+            (RegistryReference::Id(id), true) => id.encode(writer),
+            (RegistryReference::Inline(_), true) => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Inline chat types were added in 1.20.5.",
+            )),
+            (reference, false) => reference.encode(writer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip<T>(value: &T, version: ProtocolVersion) -> Vec<u8>
+    where
+        T: VersionedEncodable + VersionedDecodable + PartialEq + std::fmt::Debug,
+    {
+        let mut buf = Vec::new();
+        value.encode_versioned(&mut buf, version).unwrap();
+
+        let mut reader = &buf[..];
+        assert_eq!(&T::decode_versioned(&mut reader, version).unwrap(), value);
+        assert!(reader.is_empty());
+
+        buf
+    }
+
+    fn chat_type() -> ChatType {
+        ChatType {
+            chat: ChatTypeDecoration {
+                translation_key: "chat.type.text".into(),
+                parameters: vec![VarInt(0), VarInt(2)],
+                style: crate::nbt!({}),
+            },
+            narration: ChatTypeDecoration {
+                translation_key: "chat.type.text.narrate".into(),
+                parameters: vec![VarInt(0), VarInt(2)],
+                style: crate::nbt!({ "color": "red" }),
+            },
+        }
+    }
+
+    #[test]
+    fn chat_type_references() {
+        let id = ChatTypeReference(RegistryReference::Id(VarInt(4)));
+        let inline = ChatTypeReference(RegistryReference::Inline(chat_type()));
+
+        // the id isn't offset before inline chat types were added.
+        assert_eq!(round_trip(&id, ProtocolVersion::V1_19_3), [4]);
+        assert_eq!(round_trip(&id, ProtocolVersion::V1_20_3), [4]);
+        assert!(inline
+            .encode_versioned(&mut Vec::new(), ProtocolVersion::V1_20_3)
+            .is_err());
+
+        for version in [ProtocolVersion::V1_20_5, ProtocolVersion::V1_21] {
+            assert_eq!(round_trip(&id, version), [5]);
+            assert_eq!(round_trip(&inline, version)[0], 0);
+        }
+    }
+
+    #[test]
+    fn previous_messages_and_filters() {
+        for message in [
+            PreviousMessage::Id(3),
+            PreviousMessage::Signature(vec![1; 256].into()),
+        ] {
+            let mut buf = Vec::new();
+            message.encode(&mut buf).unwrap();
+            assert_eq!(PreviousMessage::decode(&mut &buf[..]).unwrap(), message);
+        }
+
+        let mut buf = Vec::new();
+        PreviousMessage::Id(3).encode(&mut buf).unwrap();
+        assert_eq!(buf, [4]);
+
+        for filter in [
+            FilterType::PassThrough,
+            FilterType::FullyFiltered,
+            FilterType::PartiallyFiltered(BitSet(vec![3])),
+        ] {
+            let mut buf = Vec::new();
+            filter.encode(&mut buf).unwrap();
+            assert_eq!(FilterType::decode(&mut &buf[..]).unwrap(), filter);
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use proc_macros::MinecraftPacket;
+
+use crate::{
+    datatypes::bitset::BitSet,
+    nbt::{self, OptionalValue},
+    packets::{Decodable, Encodable},
+    VarInt, VarLong,
+};
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct ChunkData {
+    #[versioned]
+    pub heightmaps: nbt::Value,
+    // the chunk sections from the bottom of the world up, each with its block count, block states and biomes.
+    pub data: Vec<u8>,
+    #[versioned]
+    pub block_entities: Vec<BlockEntity>,
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct BlockEntity {
+    pub packed_xz: u8,
+    pub y: i16,
+    pub ty: VarInt,
+    #[versioned]
+    pub data: OptionalValue,
+}
+
+impl BlockEntity {
+    // the coordinates inside of the chunk, from 0 to 15.
+    pub fn x(&self) -> u8 {
+        self.packed_xz >> 4
+    }
+
+    pub fn z(&self) -> u8 {
+        self.packed_xz & 15
+    }
+}
+
+// bit n of a mask is the section n - 1 counted from the bottom of the world, since light extends one section below
+// and above it. every section with a bit set in a light mask has a 2048 byte array, in order.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct LightData {
+    #[until(V1_20)]
+    pub trust_edges: Option<bool>,
+    pub sky_light_mask: BitSet,
+    pub block_light_mask: BitSet,
+    pub empty_sky_light_mask: BitSet,
+    pub empty_block_light_mask: BitSet,
+    pub sky_light_arrays: Vec<Vec<u8>>,
+    pub block_light_arrays: Vec<Vec<u8>>,
+}
+
+// a changed block inside of a chunk section, sent as a VarLong with the block state above the 12 position bits.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SectionBlock {
+    pub block_state: i32,
+    // the coordinates inside of the section, from 0 to 15.
+    pub x: u8,
+    pub y: u8,
+    pub z: u8,
+}
+
+impl Decodable for SectionBlock {
+    fn decode<R: std::io::Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let value = VarLong::decode(reader)?.0;
+
+        Ok(SectionBlock {
+            block_state: (value >> 12) as i32,
+            x: (value >> 8 & 15) as u8,
+            y: (value & 15) as u8,
+            z: (value >> 4 & 15) as u8,
+        })
+    }
+}
+
+impl Encodable for SectionBlock {
+    fn encode<W: std::io::Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        let value = (self.block_state as i64) << 12
+            | (self.x as i64 & 15) << 8
+            | (self.z as i64 & 15) << 4
+            | (self.y as i64 & 15);
+
+        VarLong(value).encode(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::packet::ProtocolVersion;
+
+    use crate::packets::{VersionedDecodable, VersionedEncodable};
+
+    use super::*;
+
+    fn round_trip<T>(value: &T, version: ProtocolVersion) -> T
+    where
+        T: VersionedEncodable + VersionedDecodable,
+    {
+        let mut buf = Vec::new();
+        value.encode_versioned(&mut buf, version).unwrap();
+
+        let mut reader = &buf[..];
+        let decoded = T::decode_versioned(&mut reader, version).unwrap();
+        assert!(reader.is_empty());
+
+        decoded
+    }
+
+    #[test]
+    fn chunk_data() {
+        let chunk = ChunkData {
+            heightmaps: crate::nbt!({ "MOTION_BLOCKING": nbt::Value::LongArray(vec![1, 2]) }),
+            data: vec![1, 2, 3],
+            block_entities: vec![
+                BlockEntity {
+                    packed_xz: 0x3A,
+                    y: 64,
+                    ty: VarInt(7),
+                    data: OptionalValue(None),
+                },
+                BlockEntity {
+                    packed_xz: 0,
+                    y: -1,
+                    ty: VarInt(1),
+                    data: OptionalValue(Some(crate::nbt!({ "a": 1i32 }))),
+                },
+            ],
+        };
+        assert_eq!(
+            (chunk.block_entities[0].x(), chunk.block_entities[0].z()),
+            (3, 10)
+        );
+
+        for version in [
+            ProtocolVersion::V1_19_4,
+            ProtocolVersion::V1_20,
+            ProtocolVersion::V1_20_2,
+            ProtocolVersion::V1_21,
+        ] {
+            assert_eq!(round_trip(&chunk, version), chunk);
+        }
+    }
+
+    #[test]
+    fn light_data_trusts_edges_until_1_20() {
+        let mut mask = BitSet::default();
+        mask.set(1, true);
+        mask.set(70, true);
+
+        let light = |trust_edges| LightData {
+            trust_edges,
+            sky_light_mask: mask.clone(),
+            block_light_mask: BitSet::default(),
+            empty_sky_light_mask: BitSet::default(),
+            empty_block_light_mask: mask.clone(),
+            sky_light_arrays: vec![vec![0; 2048]; 2],
+            block_light_arrays: Vec::new(),
+        };
+
+        let mut buf = Vec::new();
+        light(Some(true))
+            .encode_versioned(&mut buf, ProtocolVersion::V1_19_4)
+            .unwrap();
+        assert_eq!(buf[0], 1);
+        assert_eq!(
+            round_trip(&light(Some(true)), ProtocolVersion::V1_19_4),
+            light(Some(true))
+        );
+        assert!(light(None)
+            .encode_versioned(&mut Vec::new(), ProtocolVersion::V1_19_4)
+            .is_err());
+
+        let mut since_1_20 = Vec::new();
+        light(Some(true))
+            .encode_versioned(&mut since_1_20, ProtocolVersion::V1_20)
+            .unwrap();
+        assert_eq!(since_1_20, buf[1..]);
+        assert_eq!(
+            round_trip(&light(None), ProtocolVersion::V1_20),
+            light(None)
+        );
+    }
+
+    #[test]
+    fn section_blocks_are_packed_into_a_var_long() {
+        let block = SectionBlock {
+            block_state: 24000,
+            x: 15,
+            y: 3,
+            z: 9,
+        };
+
+        // 24000 << 12 | 15 << 8 | 9 << 4 | 3
+        let mut buf = Vec::new();
+        block.encode(&mut buf).unwrap();
+        assert_eq!(buf, [0x93, 0x9F, 0xF0, 0x2E]);
+        assert_eq!(SectionBlock::decode(&mut &buf[..]).unwrap(), block);
+    }
+}
@@ -0,0 +1,611 @@
+use std::io::{Read, Write};
+
+use types::packet::ProtocolVersion;
+
+use crate::{
+    identifier::Identifier,
+    packets::{Decodable, Encodable, VersionedDecodable, VersionedEncodable},
+    VarInt,
+};
+
+const NODE_TYPE: u8 = 0x03;
+const EXECUTABLE: u8 = 0x04;
+const HAS_REDIRECT: u8 = 0x08;
+const HAS_SUGGESTIONS: u8 = 0x10;
+
+const MIN: u8 = 0x01;
+const MAX: u8 = 0x02;
+
+// every argument parser in registry order, with the version it was added in and the one it was removed in. the id of
+// a parser is its index among the parsers that exist in a version.
+const PARSERS: &[(&str, ProtocolVersion, Option<ProtocolVersion>)] = &[
+    ("brigadier:bool", ProtocolVersion::V1_19, None),
+    ("brigadier:float", ProtocolVersion::V1_19, None),
+    ("brigadier:double", ProtocolVersion::V1_19, None),
+    ("brigadier:integer", ProtocolVersion::V1_19, None),
+    ("brigadier:long", ProtocolVersion::V1_19, None),
+    ("brigadier:string", ProtocolVersion::V1_19, None),
+    ("minecraft:entity", ProtocolVersion::V1_19, None),
+    ("minecraft:game_profile", ProtocolVersion::V1_19, None),
+    ("minecraft:block_pos", ProtocolVersion::V1_19, None),
+    ("minecraft:column_pos", ProtocolVersion::V1_19, None),
+    ("minecraft:vec3", ProtocolVersion::V1_19, None),
+    ("minecraft:vec2", ProtocolVersion::V1_19, None),
+    ("minecraft:block_state", ProtocolVersion::V1_19, None),
+    ("minecraft:block_predicate", ProtocolVersion::V1_19, None),
+    ("minecraft:item_stack", ProtocolVersion::V1_19, None),
+    ("minecraft:item_predicate", ProtocolVersion::V1_19, None),
+    ("minecraft:color", ProtocolVersion::V1_19, None),
+    ("minecraft:component", ProtocolVersion::V1_19, None),
+    ("minecraft:style", ProtocolVersion::V1_20_3, None),
+    ("minecraft:message", ProtocolVersion::V1_19, None),
+    ("minecraft:nbt", ProtocolVersion::V1_19, None),
+    ("minecraft:nbt_tag", ProtocolVersion::V1_19, None),
+    ("minecraft:nbt_path", ProtocolVersion::V1_19, None),
+    ("minecraft:objective", ProtocolVersion::V1_19, None),
+    ("minecraft:objective_criteria", ProtocolVersion::V1_19, None),
+    ("minecraft:operation", ProtocolVersion::V1_19, None),
+    ("minecraft:particle", ProtocolVersion::V1_19, None),
+    ("minecraft:angle", ProtocolVersion::V1_19, None),
+    ("minecraft:rotation", ProtocolVersion::V1_19, None),
+    ("minecraft:scoreboard_slot", ProtocolVersion::V1_19, None),
+    ("minecraft:score_holder", ProtocolVersion::V1_19, None),
+    ("minecraft:swizzle", ProtocolVersion::V1_19, None),
+    ("minecraft:team", ProtocolVersion::V1_19, None),
+    ("minecraft:item_slot", ProtocolVersion::V1_19, None),
+    ("minecraft:item_slots", ProtocolVersion::V1_20_5, None),
+    ("minecraft:resource_location", ProtocolVersion::V1_19, None),
+    (
+        "minecraft:mob_effect",
+        ProtocolVersion::V1_19,
+        Some(ProtocolVersion::V1_19_3),
+    ),
+    ("minecraft:function", ProtocolVersion::V1_19, None),
+    ("minecraft:entity_anchor", ProtocolVersion::V1_19, None),
+    ("minecraft:int_range", ProtocolVersion::V1_19, None),
+    ("minecraft:float_range", ProtocolVersion::V1_19, None),
+    (
+        "minecraft:item_enchantment",
+        ProtocolVersion::V1_19,
+        Some(ProtocolVersion::V1_19_3),
+    ),
+    (
+        "minecraft:entity_summon",
+        ProtocolVersion::V1_19,
+        Some(ProtocolVersion::V1_19_3),
+    ),
+    ("minecraft:dimension", ProtocolVersion::V1_19, None),
+    ("minecraft:gamemode", ProtocolVersion::V1_19_3, None),
+    ("minecraft:time", ProtocolVersion::V1_19, None),
+    ("minecraft:resource_or_tag", ProtocolVersion::V1_19, None),
+    (
+        "minecraft:resource_or_tag_key",
+        ProtocolVersion::V1_19_3,
+        None,
+    ),
+    ("minecraft:resource", ProtocolVersion::V1_19, None),
+    ("minecraft:resource_key", ProtocolVersion::V1_19_3, None),
+    ("minecraft:template_mirror", ProtocolVersion::V1_19, None),
+    ("minecraft:template_rotation", ProtocolVersion::V1_19, None),
+    ("minecraft:heightmap", ProtocolVersion::V1_19_4, None),
+    ("minecraft:loot_table", ProtocolVersion::V1_20_5, None),
+    ("minecraft:loot_predicate", ProtocolVersion::V1_20_5, None),
+    ("minecraft:loot_modifier", ProtocolVersion::V1_20_5, None),
+    ("minecraft:uuid", ProtocolVersion::V1_19, None),
+];
+
+fn parsers(version: ProtocolVersion) -> impl Iterator<Item = &'static str> {
+    PARSERS
+        .iter()
+        .filter(move |(_, since, until)| {
+            version >= *since && until.is_none_or(|until| version < until)
+        })
+        .map(|(name, _, _)| *name)
+}
+
+pub fn parser_name(id: i32, version: ProtocolVersion) -> Option<&'static str> {
+    parsers(version).nth(id.try_into().ok()?)
+}
+
+pub fn parser_id(name: &str, version: ProtocolVersion) -> Option<i32> {
+    parsers(version)
+        .position(|parser| parser == name)
+        .map(|id| id as i32)
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum StringType {
+    SingleWord = 0,
+    QuotablePhrase = 1,
+    GreedyPhrase = 2,
+}
+
+impl Decodable for StringType {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let ty = VarInt::decode(reader)?;
+
+        Ok(match ty.0 {
+            0 => Self::SingleWord,
+            1 => Self::QuotablePhrase,
+            2 => Self::GreedyPhrase,
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid string type, {}", ty.0),
+            ))?,
+        })
+    }
+}
+
+impl Encodable for StringType {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        VarInt(*self as i32).encode(writer)
+    }
+}
+
+// the parsers with properties, the others are only known by their name.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ArgumentParser {
+    Bool,
+    Float { min: Option<f32>, max: Option<f32> },
+    Double { min: Option<f64>, max: Option<f64> },
+    Integer { min: Option<i32>, max: Option<i32> },
+    Long { min: Option<i64>, max: Option<i64> },
+    String(StringType),
+    Entity { single: bool, players_only: bool },
+    ScoreHolder { allow_multiple: bool },
+    // the minimum in ticks was added in 1.19.4, before that it's always 0.
+    Time { min: i32 },
+    ResourceOrTag { registry: Identifier },
+    ResourceOrTagKey { registry: Identifier },
+    Resource { registry: Identifier },
+    ResourceKey { registry: Identifier },
+    Other(Identifier),
+}
+
+impl ArgumentParser {
+    pub fn name(&self) -> String {
+        match self {
+            Self::Bool => "brigadier:bool".to_owned(),
+            Self::Float { .. } => "brigadier:float".to_owned(),
+            Self::Double { .. } => "brigadier:double".to_owned(),
+            Self::Integer { .. } => "brigadier:integer".to_owned(),
+            Self::Long { .. } => "brigadier:long".to_owned(),
+            Self::String(_) => "brigadier:string".to_owned(),
+            Self::Entity { .. } => "minecraft:entity".to_owned(),
+            Self::ScoreHolder { .. } => "minecraft:score_holder".to_owned(),
+            Self::Time { .. } => "minecraft:time".to_owned(),
+            Self::ResourceOrTag { .. } => "minecraft:resource_or_tag".to_owned(),
+            Self::ResourceOrTagKey { .. } => "minecraft:resource_or_tag_key".to_owned(),
+            Self::Resource { .. } => "minecraft:resource".to_owned(),
+            Self::ResourceKey { .. } => "minecraft:resource_key".to_owned(),
+            Self::Other(name) => name.to_string(),
+        }
+    }
+}
+
+// numeric parsers send a flags byte and then only the bounds that are set.
+fn decode_bounds<T: Decodable, R: Read>(
+    reader: &mut R,
+) -> Result<(Option<T>, Option<T>), std::io::Error> {
+    let flags = u8::decode(reader)?;
+
+    let min = if flags & MIN != 0 {
+        Some(T::decode(reader)?)
+    } else {
+        None
+    };
+    let max = if flags & MAX != 0 {
+        Some(T::decode(reader)?)
+    } else {
+        None
+    };
+
+    Ok((min, max))
+}
+
+fn encode_bounds<T: Encodable, W: Write>(
+    min: &Option<T>,
+    max: &Option<T>,
+    writer: &mut W,
+) -> Result<(), std::io::Error> {
+    let flags = (min.is_some() as u8 * MIN) | (max.is_some() as u8 * MAX);
+    flags.encode(writer)?;
+
+    if let Some(min) = min {
+        min.encode(writer)?;
+    }
+
+    if let Some(max) = max {
+        max.encode(writer)?;
+    }
+
+    Ok(())
+}
+
+impl VersionedDecodable for ArgumentParser {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        let id = VarInt::decode(reader)?.0;
+
+        let name = parser_name(id, version).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Unknown argument parser, {}", id),
+            )
+        })?;
+
+        Ok(match name {
+            "brigadier:bool" => Self::Bool,
+            "brigadier:float" => {
+                let (min, max) = decode_bounds(reader)?;
+                Self::Float { min, max }
+            }
+            "brigadier:double" => {
+                let (min, max) = decode_bounds(reader)?;
+                Self::Double { min, max }
+            }
+            "brigadier:integer" => {
+                let (min, max) = decode_bounds(reader)?;
+                Self::Integer { min, max }
+            }
+            "brigadier:long" => {
+                let (min, max) = decode_bounds(reader)?;
+                Self::Long { min, max }
+            }
+            "brigadier:string" => Self::String(StringType::decode(reader)?),
+            "minecraft:entity" => {
+                let flags = u8::decode(reader)?;
+
+                Self::Entity {
+                    single: flags & 0x01 != 0,
+                    players_only: flags & 0x02 != 0,
+                }
+            }
+            "minecraft:score_holder" => Self::ScoreHolder {
+                allow_multiple: u8::decode(reader)? & 0x01 != 0,
+            },
+            "minecraft:time" if version >= ProtocolVersion::V1_19_4 => Self::Time {
+                min: i32::decode(reader)?,
+            },
+            "minecraft:time" => Self::Time { min: 0 },
+            "minecraft:resource_or_tag" => Self::ResourceOrTag {
+                registry: Identifier::decode(reader)?,
+            },
+            "minecraft:resource_or_tag_key" => Self::ResourceOrTagKey {
+                registry: Identifier::decode(reader)?,
+            },
+            "minecraft:resource" => Self::Resource {
+                registry: Identifier::decode(reader)?,
+            },
+            "minecraft:resource_key" => Self::ResourceKey {
+                registry: Identifier::decode(reader)?,
+            },
+            _ => Self::Other(Identifier::from(name)),
+        })
+    }
+}
+
+impl VersionedEncodable for ArgumentParser {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        let name = self.name();
+
+        let id = parser_id(&name, version).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                format!(
+                    "Argument parser {} doesn't exist in protocol version {}",
+                    name, version.0
+                ),
+            )
+        })?;
+        VarInt(id).encode(writer)?;
+
+        match self {
+            Self::Float { min, max } => encode_bounds(min, max, writer),
+            Self::Double { min, max } => encode_bounds(min, max, writer),
+            Self::Integer { min, max } => encode_bounds(min, max, writer),
+            Self::Long { min, max } => encode_bounds(min, max, writer),
+            Self::String(ty) => ty.encode(writer),
+            Self::Entity {
+                single,
+                players_only,
+            } => (*single as u8 | (*players_only as u8) << 1).encode(writer),
+            Self::ScoreHolder { allow_multiple } => (*allow_multiple as u8).encode(writer),
+            Self::Time { min } if version >= ProtocolVersion::V1_19_4 => min.encode(writer),
+            Self::ResourceOrTag { registry }
+            | Self::ResourceOrTagKey { registry }
+            | Self::Resource { registry }
+            | Self::ResourceKey { registry } => registry.encode(writer),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum CommandNodeType {
+    Root,
+    Literal {
+        name: String,
+    },
+    // the suggestions type asks for suggestions from somewhere else, e.g. minecraft:ask_server.
+    Argument {
+        name: String,
+        parser: ArgumentParser,
+        suggestions: Option<Identifier>,
+    },
+}
+
+// children and redirects are indexes into the node list of the Commands packet.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CommandNode {
+    pub executable: bool,
+    pub children: Vec<VarInt>,
+    pub redirect: Option<VarInt>,
+    pub ty: CommandNodeType,
+}
+
+impl VersionedDecodable for CommandNode {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        let flags = u8::decode(reader)?;
+        let children = Vec::<VarInt>::decode(reader)?;
+
+        let redirect = if flags & HAS_REDIRECT != 0 {
+            Some(VarInt::decode(reader)?)
+        } else {
+            None
+        };
+
+        let ty = match flags & NODE_TYPE {
+            0 => CommandNodeType::Root,
+            1 => CommandNodeType::Literal {
+                name: String::decode(reader)?,
+            },
+            2 => CommandNodeType::Argument {
+                name: String::decode(reader)?,
+                parser: ArgumentParser::decode_versioned(reader, version)?,
+                suggestions: if flags & HAS_SUGGESTIONS != 0 {
+                    Some(Identifier::decode(reader)?)
+                } else {
+                    None
+                },
+            },
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid command node type, {}", flags & NODE_TYPE),
+            ))?,
+        };
+
+        Ok(CommandNode {
+            executable: flags & EXECUTABLE != 0,
+            children,
+            redirect,
+            ty,
+        })
+    }
+}
+
+impl VersionedEncodable for CommandNode {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        let mut flags = match &self.ty {
+            CommandNodeType::Root => 0,
+            CommandNodeType::Literal { .. } => 1,
+            CommandNodeType::Argument { suggestions, .. } => {
+                2 | (suggestions.is_some() as u8 * HAS_SUGGESTIONS)
+            }
+        };
+        flags |= self.executable as u8 * EXECUTABLE;
+        flags |= self.redirect.is_some() as u8 * HAS_REDIRECT;
+
+        flags.encode(writer)?;
+        self.children.encode(writer)?;
+
+        if let Some(redirect) = &self.redirect {
+            redirect.encode(writer)?;
+        }
+
+        match &self.ty {
+            CommandNodeType::Root => Ok(()),
+            CommandNodeType::Literal { name } => name.encode(writer),
+            CommandNodeType::Argument {
+                name,
+                parser,
+                suggestions,
+            } => {
+                name.encode(writer)?;
+                parser.encode_versioned(writer, version)?;
+
+                match suggestions {
+                    Some(suggestions) => suggestions.encode(writer),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::packets::clientbound::play::Commands;
+
+    use super::*;
+
+    fn argument(name: &str, parser: ArgumentParser, suggestions: Option<&str>) -> CommandNodeType {
+        CommandNodeType::Argument {
+            name: name.to_owned(),
+            parser,
+            suggestions: suggestions.map(Identifier::from),
+        }
+    }
+
+    // /tp <target> and /repeat <n> redirecting back to the root, plus a few parsers with properties.
+    fn tree() -> Commands {
+        let node = |executable, children: &[i32], redirect, ty| CommandNode {
+            executable,
+            children: children.iter().copied().map(VarInt).collect(),
+            redirect,
+            ty,
+        };
+
+        Commands {
+            root_index: VarInt(0),
+            nodes: vec![
+                node(false, &[1, 3, 4, 5, 6], None, CommandNodeType::Root),
+                node(
+                    false,
+                    &[2],
+                    None,
+                    CommandNodeType::Literal { name: "tp".into() },
+                ),
+                node(
+                    true,
+                    &[],
+                    None,
+                    argument(
+                        "target",
+                        ArgumentParser::Entity {
+                            single: true,
+                            players_only: false,
+                        },
+                        None,
+                    ),
+                ),
+                node(
+                    true,
+                    &[],
+                    Some(VarInt(0)),
+                    argument(
+                        "n",
+                        ArgumentParser::Integer {
+                            min: Some(0),
+                            max: None,
+                        },
+                        Some("minecraft:ask_server"),
+                    ),
+                ),
+                node(
+                    true,
+                    &[],
+                    None,
+                    argument(
+                        "pos",
+                        ArgumentParser::Other("minecraft:block_pos".into()),
+                        None,
+                    ),
+                ),
+                node(
+                    true,
+                    &[],
+                    None,
+                    argument("t", ArgumentParser::Time { min: 0 }, None),
+                ),
+                node(
+                    true,
+                    &[],
+                    None,
+                    argument(
+                        "biome",
+                        ArgumentParser::ResourceOrTag {
+                            registry: "minecraft:worldgen/biome".into(),
+                        },
+                        None,
+                    ),
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn parser_ids_depend_on_the_version() {
+        assert_eq!(
+            parser_name(0, ProtocolVersion::V1_19),
+            Some("brigadier:bool")
+        );
+        assert_eq!(
+            parser_id("minecraft:uuid", ProtocolVersion::V1_19_3),
+            Some(47)
+        );
+        assert_eq!(
+            parser_id("minecraft:uuid", ProtocolVersion::V1_20),
+            Some(48)
+        );
+        assert_eq!(
+            parser_id("minecraft:uuid", ProtocolVersion::V1_20_3),
+            Some(49)
+        );
+        assert_eq!(
+            parser_id("minecraft:uuid", ProtocolVersion::V1_21),
+            Some(53)
+        );
+        assert_eq!(
+            parser_id("minecraft:mob_effect", ProtocolVersion::V1_19_3),
+            None
+        );
+    }
+
+    #[test]
+    fn node_graph_round_trip() {
+        for version in [
+            ProtocolVersion::V1_19,
+            ProtocolVersion::V1_19_3,
+            ProtocolVersion::V1_20_3,
+            ProtocolVersion::V1_21,
+        ] {
+            let mut buf = Vec::new();
+            tree().encode_versioned(&mut buf, version).unwrap();
+
+            let mut reader = &buf[..];
+            assert_eq!(
+                Commands::decode_versioned(&mut reader, version).unwrap(),
+                tree()
+            );
+            assert!(reader.is_empty());
+        }
+
+        let mut buf = Vec::new();
+        tree().encode(&mut buf).unwrap();
+
+        // 7 nodes, the root has no flags and 5 children.
+        assert_eq!(buf[..3], [7, 0, 5]);
+        // past the count and the root, tp and target nodes is the argument with a redirect and suggestions.
+        let repeat = 1 + 7 + 6 + 11;
+        assert_eq!(buf[repeat], 2 | EXECUTABLE | HAS_REDIRECT | HAS_SUGGESTIONS);
+    }
+
+    #[test]
+    fn unknown_parsers_are_rejected() {
+        let style = Commands {
+            root_index: VarInt(0),
+            nodes: vec![CommandNode {
+                executable: true,
+                children: Vec::new(),
+                redirect: None,
+                ty: argument("s", ArgumentParser::Other("minecraft:style".into()), None),
+            }],
+        };
+
+        assert!(style
+            .encode_versioned(&mut Vec::new(), ProtocolVersion::V1_20_2)
+            .is_err());
+        assert!(style
+            .encode_versioned(&mut Vec::new(), ProtocolVersion::V1_20_3)
+            .is_ok());
+        assert!(Commands::decode_versioned(
+            &mut &[1, 2, 0, 1, b's', 0x7F, 0][..],
+            ProtocolVersion::V1_21
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,64 @@
+use proc_macros::MinecraftPacket;
+
+use crate::{
+    identifier::Identifier,
+    nbt,
+    packets::{Decodable, Encodable},
+    VarInt,
+};
+
+// one entry of a synced registry, e.g. minecraft:plains in minecraft:worldgen/biome. the data is missing when the
+// client already knows the entry from a known pack.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct RegistryEntry {
+    pub id: Identifier,
+    pub data: Option<nbt::Value>,
+}
+
+// a data pack that both sides might have, so its registry entries don't need to be sent.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct KnownPack {
+    pub namespace: String,
+    pub id: String,
+    pub version: String,
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ResourcePackResult {
+    SuccessfullyDownloaded = 0,
+    Declined = 1,
+    FailedToDownload = 2,
+    Accepted = 3,
+    // the ones below were added in 1.20.3.
+    Downloaded = 4,
+    InvalidUrl = 5,
+    FailedToReload = 6,
+    Discarded = 7,
+}
+
+impl Decodable for ResourcePackResult {
+    fn decode<R: std::io::Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let result = VarInt::decode(reader)?;
+
+        Ok(match result.0 {
+            0 => Self::SuccessfullyDownloaded,
+            1 => Self::Declined,
+            2 => Self::FailedToDownload,
+            3 => Self::Accepted,
+            4 => Self::Downloaded,
+            5 => Self::InvalidUrl,
+            6 => Self::FailedToReload,
+            7 => Self::Discarded,
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid resource pack result, {}", result.0),
+            ))?,
+        })
+    }
+}
+
+impl Encodable for ResourcePackResult {
+    fn encode<W: std::io::Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        VarInt(*self as i32).encode(writer)
+    }
+}
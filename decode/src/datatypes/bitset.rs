@@ -0,0 +1,27 @@
+// a bit set sent as an array of longs, bit n is stored in long n / 64.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BitSet(pub Vec<i64>);
+
+impl BitSet {
+    pub fn get(&self, index: usize) -> bool {
+        self.0
+            .get(index / 64)
+            .is_some_and(|long| long & (1 << (index % 64)) != 0)
+    }
+
+    pub fn set(&mut self, index: usize, value: bool) {
+        if index / 64 >= self.0.len() {
+            if !value {
+                return;
+            }
+
+            self.0.resize(index / 64 + 1, 0);
+        }
+
+        if value {
+            self.0[index / 64] |= 1 << (index % 64);
+        } else {
+            self.0[index / 64] &= !(1 << (index % 64));
+        }
+    }
+}
@@ -3,9 +3,14 @@ use std::io::Read;
 use byteorder::{BigEndian, ReadBytesExt};
 use uuid::Uuid;
 
-use crate::{packets::Decodable, VarInt};
+use types::packet::ProtocolVersion;
 
-use super::sized::SizedVec;
+use crate::{
+    packets::{Decodable, VersionedDecodable},
+    VarInt, VarLong,
+};
+
+use super::{bitset::BitSet, remaining::RemainingBytes, sized::SizedVec};
 
 impl Decodable for VarInt {
     fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
@@ -25,12 +30,52 @@ impl Decodable for VarInt {
             if byte[0] & 0b10000000 == 0 {
                 break;
             }
+
+            if shift >= 35 {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "VarInt is longer than 5 bytes.",
+                ));
+            }
         }
 
         Ok(VarInt(result))
     }
 }
 
+impl Decodable for VarLong {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let mut result = 0;
+        let mut shift = 0;
+
+        loop {
+            let byte = reader.read_u8()?;
+
+            result |= ((byte & 0b01111111) as i64) << shift;
+            shift += 7;
+
+            if byte & 0b10000000 == 0 {
+                break;
+            }
+
+            if shift >= 70 {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "VarLong is longer than 10 bytes.",
+                ));
+            }
+        }
+
+        Ok(VarLong(result))
+    }
+}
+
+impl Decodable for bool {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        Ok(reader.read_u8()? != 0)
+    }
+}
+
 impl Decodable for u8 {
     fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
         reader.read_u8()
@@ -43,12 +88,24 @@ impl Decodable for i8 {
     }
 }
 
+impl Decodable for u16 {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        reader.read_u16::<BigEndian>()
+    }
+}
+
 impl Decodable for i16 {
     fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
         reader.read_i16::<BigEndian>()
     }
 }
 
+impl Decodable for i32 {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        reader.read_i32::<BigEndian>()
+    }
+}
+
 impl Decodable for i64 {
     fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
         reader.read_i64::<BigEndian>()
@@ -69,12 +126,18 @@ impl Decodable for f64 {
 
 impl Decodable for String {
     fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
-        let string_len = reader.read_i8()?;
+        let string_len = VarInt::decode(reader)?.0;
 
-        let mut buf = Vec::with_capacity(string_len as usize);
-        buf.resize(string_len as usize, 0);
+        let string_len: usize = string_len.try_into().map_err(|_| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Negative string length, {}", string_len),
+            )
+        })?;
 
-        reader.read(&mut buf[..])?;
+        let mut buf = vec![0; string_len];
+
+        reader.read_exact(&mut buf[..])?;
 
         String::from_utf8(buf).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
     }
@@ -83,24 +146,107 @@ impl Decodable for String {
 impl Decodable for Uuid {
     fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
         let buffer: &mut [u8; 16] = &mut [0; 16];
-        reader.read(buffer)?;
+        reader.read_exact(buffer)?;
 
         Ok(Uuid::from_bytes(*buffer))
     }
 }
 
-impl Decodable for Option<Uuid> {
+impl<T> Decodable for Option<T>
+where
+    T: Decodable,
+{
     fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
         let result = reader.read_u8()?;
 
         if result == 0x01 {
-            return Ok(Some(Uuid::decode(reader)?));
+            return Ok(Some(T::decode(reader)?));
         }
 
         Ok(None)
     }
 }
 
+// arrays are prefixed with their length as a VarInt.
+fn decode_length<R: Read>(reader: &mut R) -> Result<usize, std::io::Error> {
+    let len = VarInt::decode(reader)?.0;
+
+    len.try_into().map_err(|_| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Negative array length, {}", len),
+        )
+    })
+}
+
+impl<T> Decodable for Vec<T>
+where
+    T: Decodable,
+{
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let len = decode_length(reader)?;
+
+        // don't trust the length for the allocation, it comes straight from the network.
+        let mut vec = Vec::with_capacity(len.min(1024));
+
+        for _ in 0..len {
+            vec.push(T::decode(reader)?);
+        }
+
+        Ok(vec)
+    }
+}
+
+impl<T> VersionedDecodable for Option<T>
+where
+    T: VersionedDecodable,
+{
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        if bool::decode(reader)? {
+            return Ok(Some(T::decode_versioned(reader, version)?));
+        }
+
+        Ok(None)
+    }
+}
+
+impl<T> VersionedDecodable for Vec<T>
+where
+    T: VersionedDecodable,
+{
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        let len = decode_length(reader)?;
+        let mut vec = Vec::with_capacity(len.min(1024));
+
+        for _ in 0..len {
+            vec.push(T::decode_versioned(reader, version)?);
+        }
+
+        Ok(vec)
+    }
+}
+
+impl Decodable for BitSet {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        Ok(BitSet(Vec::<i64>::decode(reader)?))
+    }
+}
+
+impl Decodable for RemainingBytes {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+
+        Ok(RemainingBytes(buf))
+    }
+}
+
 impl<T, const S: usize> Decodable for SizedVec<T, S>
 where
     T: Decodable + Clone,
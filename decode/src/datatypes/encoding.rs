@@ -3,13 +3,34 @@ use std::io::Write;
 use byteorder::WriteBytesExt;
 use uuid::Uuid;
 
-use crate::{packets::Encodable, VarInt};
+use types::packet::ProtocolVersion;
 
-use super::sized::SizedVec;
+use crate::{
+    packets::{Encodable, VersionedEncodable},
+    VarInt, VarLong,
+};
+
+use super::{bitset::BitSet, remaining::RemainingBytes, sized::SizedVec};
 
 impl Encodable for VarInt {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
-        let mut remaining = self.0;
+        // negative values are written as their two's complement, which always takes all 5 bytes.
+        let mut remaining = self.0 as u32;
+        while remaining >= 0b10000000 {
+            let byte = (remaining as u8) | 0b10000000;
+
+            writer.write_all(&[byte])?;
+            remaining >>= 7;
+        }
+        let byte = remaining as u8;
+
+        writer.write_all(&[byte])
+    }
+}
+
+impl Encodable for VarLong {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        let mut remaining = self.0 as u64;
         while remaining >= 0b10000000 {
             let byte = (remaining as u8) | 0b10000000;
 
@@ -22,6 +43,12 @@ impl Encodable for VarInt {
     }
 }
 
+impl Encodable for bool {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        writer.write_u8(*self as u8)
+    }
+}
+
 impl Encodable for u8 {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
         writer.write_all(&[*self])
@@ -34,12 +61,24 @@ impl Encodable for i8 {
     }
 }
 
+impl Encodable for u16 {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        writer.write_all(&self.to_be_bytes())
+    }
+}
+
 impl Encodable for i16 {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
         writer.write_all(&self.to_be_bytes())
     }
 }
 
+impl Encodable for i32 {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        writer.write_all(&self.to_be_bytes())
+    }
+}
+
 impl Encodable for i64 {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
         writer.write_all(&self.to_be_bytes())
@@ -60,32 +99,110 @@ impl Encodable for f64 {
 
 impl Encodable for String {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
-        writer.write(&[self.len() as u8])?;
-        writer.write(self.as_bytes())?;
-        Ok(())
+        let len = self.len().try_into().map_err(|_| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "String is too long.")
+        })?;
+
+        VarInt(len).encode(writer)?;
+        writer.write_all(self.as_bytes())
     }
 }
 
 impl Encodable for Uuid {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
-        writer.write(self.as_bytes())?;
+        writer.write_all(self.as_bytes())
+    }
+}
+
+impl<T> Encodable for Option<T>
+where
+    T: Encodable,
+{
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        let is_present = if self.is_some() { 0x01 } else { 0x00 };
+        writer.write_u8(is_present)?;
+
+        if let Some(value) = self {
+            value.encode(writer)?;
+        }
+
         Ok(())
     }
 }
 
-impl Encodable for Option<Uuid> {
+fn encode_length<W: Write>(len: usize, writer: &mut W) -> Result<(), std::io::Error> {
+    let len = len
+        .try_into()
+        .map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Array is too long."))?;
+
+    VarInt(len).encode(writer)
+}
+
+impl<T> Encodable for Vec<T>
+where
+    T: Encodable,
+{
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
-        let has_unique_id = if self.is_some() { 0x01 } else { 0x00 };
-        writer.write_u8(has_unique_id)?;
+        encode_length(self.len(), writer)?;
+
+        for element in self.iter() {
+            element.encode(writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> VersionedEncodable for Option<T>
+where
+    T: VersionedEncodable,
+{
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        self.is_some().encode(writer)?;
+
+        if let Some(value) = self {
+            value.encode_versioned(writer, version)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> VersionedEncodable for Vec<T>
+where
+    T: VersionedEncodable,
+{
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        encode_length(self.len(), writer)?;
 
-        if let Some(id) = self {
-            id.encode(writer)?;
+        for element in self.iter() {
+            element.encode_versioned(writer, version)?;
         }
 
         Ok(())
     }
 }
 
+impl Encodable for BitSet {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        self.0.encode(writer)
+    }
+}
+
+impl Encodable for RemainingBytes {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        writer.write_all(&self.0)
+    }
+}
+
 impl<T, const S: usize> Encodable for SizedVec<T, S>
 where
     T: Encodable + Clone,
@@ -0,0 +1,28 @@
+use std::io::{Read, Write};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::packets::{Decodable, Encodable};
+
+// a lot of the protocol sends JSON documents as a regular string, these read and write them directly.
+
+pub fn decode_json<T, R>(reader: &mut R) -> Result<T, std::io::Error>
+where
+    T: DeserializeOwned,
+    R: Read,
+{
+    let json = String::decode(reader)?;
+
+    serde_json::from_str(&json).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+pub fn encode_json<T, W>(value: &T, writer: &mut W) -> Result<(), std::io::Error>
+where
+    T: Serialize,
+    W: Write,
+{
+    let json = serde_json::to_string(value)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+
+    json.encode(writer)
+}
@@ -1,3 +1,6 @@
+pub mod bitset;
 pub mod decoding;
 pub mod encoding;
+pub mod json;
+pub mod remaining;
 pub mod sized;
@@ -0,0 +1,3 @@
+// everything up to the end of the packet, for payloads that aren't length prefixed, e.g. plugin messages.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RemainingBytes(pub Vec<u8>);
@@ -29,11 +29,20 @@ where
         S
     }
 
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
         self.inner.iter()
     }
 }
 
+impl<T, const S: usize> Default for SizedVec<T, S>
+where
+    T: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T, const S: usize> From<Vec<T>> for SizedVec<T, S>
 where
     T: Clone,
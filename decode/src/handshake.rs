@@ -0,0 +1,70 @@
+use types::packet::ClientState;
+
+use crate::{
+    packets::{Decodable, Encodable},
+    VarInt,
+};
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum NextState {
+    Status = 1,
+    Login = 2,
+    // added in 1.20.5, the client is being transferred from another server but still continues with login.
+    Transfer = 3,
+}
+
+impl From<NextState> for ClientState {
+    fn from(state: NextState) -> Self {
+        match state {
+            NextState::Status => ClientState::Status,
+            NextState::Login | NextState::Transfer => ClientState::Login,
+        }
+    }
+}
+
+impl Decodable for NextState {
+    fn decode<R: std::io::Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let state = VarInt::decode(reader)?;
+
+        Ok(match state.0 {
+            1 => Self::Status,
+            2 => Self::Login,
+            3 => Self::Transfer,
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                format!("Unsupported next state, {}", state.0),
+            ))?,
+        })
+    }
+}
+
+impl Encodable for NextState {
+    fn encode<W: std::io::Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        VarInt(*self as i32).encode(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_states() {
+        for (id, state, client_state) in [
+            (1, NextState::Status, ClientState::Status),
+            (2, NextState::Login, ClientState::Login),
+            (3, NextState::Transfer, ClientState::Login),
+        ] {
+            let mut buf = Vec::new();
+            state.encode(&mut buf).unwrap();
+            assert_eq!(buf, [id]);
+            assert_eq!(NextState::decode(&mut &buf[..]).unwrap(), state);
+            assert_eq!(ClientState::from(state), client_state);
+        }
+
+        for id in [0u8, 4] {
+            let error = NextState::decode(&mut &[id][..]).unwrap_err();
+            assert_eq!(error.kind(), std::io::ErrorKind::Unsupported);
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use std::fmt::Display;
+
+use types::packet::ProtocolVersion;
+
+use crate::{
+    packets::{Decodable, Encodable, VersionedDecodable, VersionedEncodable},
+    VarInt,
+};
+
+const DEFAULT_NAMESPACE: &str = "minecraft";
+
+// a namespaced key like minecraft:stone. we keep the string exactly as it was sent, so an identifier without a
+// namespace is written back without one as well.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Identifier(pub String);
+
+impl Identifier {
+    pub fn new(namespace: &str, path: &str) -> Self {
+        Identifier(format!("{}:{}", namespace, path))
+    }
+
+    pub fn namespace(&self) -> &str {
+        match self.0.split_once(':') {
+            Some((namespace, _)) => namespace,
+            None => DEFAULT_NAMESPACE,
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        match self.0.split_once(':') {
+            Some((_, path)) => path,
+            None => &self.0,
+        }
+    }
+}
+
+impl From<&str> for Identifier {
+    fn from(value: &str) -> Self {
+        Identifier(value.to_owned())
+    }
+}
+
+impl Display for Identifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.namespace(), self.path())
+    }
+}
+
+impl Decodable for Identifier {
+    fn decode<R: std::io::Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        Ok(Identifier(String::decode(reader)?))
+    }
+}
+
+impl Encodable for Identifier {
+    fn encode<W: std::io::Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        self.0.encode(writer)
+    }
+}
+
+// a set of registry entries, either a tag or the ids of the entries. the tag is sent as type 0, otherwise the type is
+// the number of ids plus one.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdSet {
+    Tag(Identifier),
+    Ids(Vec<VarInt>),
+}
+
+impl Decodable for IdSet {
+    fn decode<R: std::io::Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let ty = VarInt::decode(reader)?.0;
+
+        if ty == 0 {
+            return Ok(Self::Tag(Identifier::decode(reader)?));
+        }
+
+        let ids = (1..ty)
+            .map(|_| VarInt::decode(reader))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self::Ids(ids))
+    }
+}
+
+impl Encodable for IdSet {
+    fn encode<W: std::io::Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        match self {
+            Self::Tag(tag) => {
+                VarInt(0).encode(writer)?;
+                tag.encode(writer)
+            }
+            Self::Ids(ids) => {
+                VarInt(ids.len() as i32 + 1).encode(writer)?;
+
+                for id in ids {
+                    id.encode(writer)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+// an entry of a registry the client may not know, either by its id or sent inline. on the wire the id is offset by one
+// and 0 means the entry follows.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RegistryReference<T> {
+    Id(VarInt),
+    Inline(T),
+}
+
+// the id as it is sent, offset by one so that 0 can mean inline.
+fn wire_id(id: &VarInt) -> Result<VarInt, std::io::Error> {
+    id.0.checked_add(1).map(VarInt).ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Registry id {} is too large to send.", id.0),
+        )
+    })
+}
+
+fn registry_id(id: VarInt) -> Result<VarInt, std::io::Error> {
+    id.0.checked_sub(1).map(VarInt).ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Invalid registry id, {}", id.0),
+        )
+    })
+}
+
+impl<T> Decodable for RegistryReference<T>
+where
+    T: Decodable,
+{
+    fn decode<R: std::io::Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let id = VarInt::decode(reader)?;
+
+        if id.0 == 0 {
+            return Ok(Self::Inline(T::decode(reader)?));
+        }
+
+        Ok(Self::Id(registry_id(id)?))
+    }
+}
+
+impl<T> Encodable for RegistryReference<T>
+where
+    T: Encodable,
+{
+    fn encode<W: std::io::Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        match self {
+            Self::Id(id) => wire_id(id)?.encode(writer),
+            Self::Inline(value) => {
+                VarInt(0).encode(writer)?;
+                value.encode(writer)
+            }
+        }
+    }
+}
+
+impl<T> VersionedDecodable for RegistryReference<T>
+where
+    T: VersionedDecodable,
+{
+    fn decode_versioned<R: std::io::Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        let id = VarInt::decode(reader)?;
+
+        if id.0 == 0 {
+            return Ok(Self::Inline(T::decode_versioned(reader, version)?));
+        }
+
+        Ok(Self::Id(registry_id(id)?))
+    }
+}
+
+impl<T> VersionedEncodable for RegistryReference<T>
+where
+    T: VersionedEncodable,
+{
+    fn encode_versioned<W: std::io::Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        match self {
+            Self::Id(id) => wire_id(id)?.encode(writer),
+            Self::Inline(value) => {
+                VarInt(0).encode(writer)?;
+                value.encode_versioned(writer, version)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_references_are_offset() {
+        let mut buf = Vec::new();
+        RegistryReference::<Identifier>::Id(VarInt(4))
+            .encode(&mut buf)
+            .unwrap();
+        RegistryReference::Inline(Identifier::from("minecraft:a"))
+            .encode(&mut buf)
+            .unwrap();
+        assert_eq!(buf[..3], [5, 0, 11]);
+
+        let mut reader = &buf[..];
+        assert_eq!(
+            RegistryReference::<Identifier>::decode(&mut reader).unwrap(),
+            RegistryReference::Id(VarInt(4))
+        );
+        assert_eq!(
+            RegistryReference::<Identifier>::decode(&mut reader).unwrap(),
+            RegistryReference::Inline(Identifier::from("minecraft:a"))
+        );
+        assert!(reader.is_empty());
+
+        assert!(RegistryReference::<Identifier>::Id(VarInt(i32::MAX))
+            .encode(&mut Vec::new())
+            .is_err());
+
+        let mut buf = Vec::new();
+        VarInt(i32::MIN).encode(&mut buf).unwrap();
+        assert!(RegistryReference::<Identifier>::decode(&mut &buf[..]).is_err());
+    }
+}
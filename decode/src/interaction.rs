@@ -0,0 +1,182 @@
+use std::io::{Read, Write};
+
+use crate::{
+    packets::{Decodable, Encodable},
+    VarInt,
+};
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Hand {
+    MainHand = 0,
+    OffHand = 1,
+}
+
+impl Decodable for Hand {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let hand = VarInt::decode(reader)?;
+
+        Ok(match hand.0 {
+            0 => Self::MainHand,
+            1 => Self::OffHand,
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid hand, {}", hand.0),
+            ))?,
+        })
+    }
+}
+
+impl Encodable for Hand {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        VarInt(*self as i32).encode(writer)
+    }
+}
+
+// Use Item On sends the face as a VarInt and Player Action as a byte.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum BlockFace {
+    Bottom = 0,
+    Top = 1,
+    North = 2,
+    South = 3,
+    West = 4,
+    East = 5,
+}
+
+impl BlockFace {
+    fn from_id(face: i32) -> Result<Self, std::io::Error> {
+        Ok(match face {
+            0 => Self::Bottom,
+            1 => Self::Top,
+            2 => Self::North,
+            3 => Self::South,
+            4 => Self::West,
+            5 => Self::East,
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid block face, {}", face),
+            ))?,
+        })
+    }
+
+    /// Reads the face as a single byte, like Player Action sends it.
+    pub fn decode_byte<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        Self::from_id(i8::decode(reader)? as i32)
+    }
+
+    pub fn encode_byte<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        (*self as i8).encode(writer)
+    }
+}
+
+impl Decodable for BlockFace {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        Self::from_id(VarInt::decode(reader)?.0)
+    }
+}
+
+impl Encodable for BlockFace {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        VarInt(*self as i32).encode(writer)
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum PlayerActionStatus {
+    StartedDigging = 0,
+    CancelledDigging = 1,
+    FinishedDigging = 2,
+    DropItemStack = 3,
+    DropItem = 4,
+    // also finishes eating or any other item that is used over time.
+    ShootArrow = 5,
+    SwapItemInHand = 6,
+}
+
+impl Decodable for PlayerActionStatus {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let status = VarInt::decode(reader)?;
+
+        Ok(match status.0 {
+            0 => Self::StartedDigging,
+            1 => Self::CancelledDigging,
+            2 => Self::FinishedDigging,
+            3 => Self::DropItemStack,
+            4 => Self::DropItem,
+            5 => Self::ShootArrow,
+            6 => Self::SwapItemInHand,
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid player action status, {}", status.0),
+            ))?,
+        })
+    }
+}
+
+impl Encodable for PlayerActionStatus {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        VarInt(*self as i32).encode(writer)
+    }
+}
+
+// the target of Interact At is relative to the entity's position.
+#[derive(PartialEq, Clone, Debug)]
+pub enum InteractAction {
+    Interact {
+        hand: Hand,
+    },
+    Attack,
+    InteractAt {
+        target_x: f32,
+        target_y: f32,
+        target_z: f32,
+        hand: Hand,
+    },
+}
+
+impl Decodable for InteractAction {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let ty = VarInt::decode(reader)?;
+
+        Ok(match ty.0 {
+            0 => Self::Interact {
+                hand: Hand::decode(reader)?,
+            },
+            1 => Self::Attack,
+            2 => Self::InteractAt {
+                target_x: f32::decode(reader)?,
+                target_y: f32::decode(reader)?,
+                target_z: f32::decode(reader)?,
+                hand: Hand::decode(reader)?,
+            },
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid interaction type, {}", ty.0),
+            ))?,
+        })
+    }
+}
+
+impl Encodable for InteractAction {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        match self {
+            Self::Interact { hand } => {
+                VarInt(0).encode(writer)?;
+                hand.encode(writer)
+            }
+            Self::Attack => VarInt(1).encode(writer),
+            Self::InteractAt {
+                target_x,
+                target_y,
+                target_z,
+                hand,
+            } => {
+                VarInt(2).encode(writer)?;
+                target_x.encode(writer)?;
+                target_y.encode(writer)?;
+                target_z.encode(writer)?;
+                hand.encode(writer)
+            }
+        }
+    }
+}
@@ -1,11 +1,57 @@
 pub mod animation;
+pub mod boss_bar;
+pub mod chat;
+pub mod chunk;
+pub mod commands;
+pub mod configuration;
 pub mod datatypes;
 pub mod decoding;
 pub mod encoding;
+pub mod handshake;
+pub mod identifier;
+pub mod interaction;
 pub mod nbt;
 pub mod packets;
+pub mod particle;
+pub mod player_info;
 pub mod position;
+pub mod profile;
+pub mod recipe;
+pub mod scoreboard;
+pub mod slot;
+pub mod sound;
+pub mod spawn;
 pub mod statistics;
+pub mod status;
+pub mod tags;
+pub mod text;
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct VarInt(i32);
+pub struct VarInt(pub i32);
+
+impl From<i32> for VarInt {
+    fn from(value: i32) -> Self {
+        VarInt(value)
+    }
+}
+
+impl From<VarInt> for i32 {
+    fn from(value: VarInt) -> Self {
+        value.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VarLong(pub i64);
+
+impl From<i64> for VarLong {
+    fn from(value: i64) -> Self {
+        VarLong(value)
+    }
+}
+
+impl From<VarLong> for i64 {
+    fn from(value: VarLong) -> Self {
+        value.0
+    }
+}
@@ -0,0 +1,220 @@
+use std::fmt::Display;
+
+use super::{Compound, Value};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NbtError {
+    MissingKey(String),
+    InvalidJson(String),
+    UnexpectedType {
+        expected: &'static str,
+        found: &'static str,
+    },
+}
+
+impl Display for NbtError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingKey(key) => write!(f, "No NBT value found for key {}", key),
+            Self::InvalidJson(json) => write!(f, "Unable to convert JSON to NBT, {}", json),
+            Self::UnexpectedType { expected, found } => {
+                write!(f, "Expected NBT {} but found {}", expected, found)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NbtError {}
+
+impl From<NbtError> for std::io::Error {
+    fn from(error: NbtError) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, error)
+    }
+}
+
+impl Value {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Byte(_) => "Byte",
+            Value::Short(_) => "Short",
+            Value::Int(_) => "Int",
+            Value::Long(_) => "Long",
+            Value::Float(_) => "Float",
+            Value::Double(_) => "Double",
+            Value::ByteArray(_) => "ByteArray",
+            Value::String(_) => "String",
+            Value::List(_) => "List",
+            Value::Compound(_) => "Compound",
+            Value::IntArray(_) => "IntArray",
+            Value::LongArray(_) => "LongArray",
+        }
+    }
+
+    fn unexpected(&self, expected: &'static str) -> NbtError {
+        NbtError::UnexpectedType {
+            expected,
+            found: self.type_name(),
+        }
+    }
+
+    pub fn as_compound(&self) -> Result<&Compound, NbtError> {
+        match self {
+            Value::Compound(compound) => Ok(compound),
+            _ => Err(self.unexpected("Compound")),
+        }
+    }
+
+    pub fn as_list(&self) -> Result<&[Value], NbtError> {
+        match self {
+            Value::List(list) => Ok(list),
+            _ => Err(self.unexpected("List")),
+        }
+    }
+
+    pub fn as_str(&self) -> Result<&str, NbtError> {
+        match self {
+            Value::String(value) => Ok(value),
+            _ => Err(self.unexpected("String")),
+        }
+    }
+
+    /// Converts every element of a list, failing on the first one that has a different type.
+    pub fn as_list_of<T>(&self) -> Result<Vec<T>, NbtError>
+    where
+        T: for<'a> TryFrom<&'a Value, Error = NbtError>,
+    {
+        self.as_list()?.iter().map(T::try_from).collect()
+    }
+
+    pub fn get(&self, key: &str) -> Result<&Value, NbtError> {
+        self.as_compound()?
+            .get(key)
+            .ok_or_else(|| NbtError::MissingKey(key.to_owned()))
+    }
+
+    pub fn get_compound(&self, key: &str) -> Result<&Compound, NbtError> {
+        self.get(key)?.as_compound()
+    }
+
+    pub fn get_list(&self, key: &str) -> Result<&[Value], NbtError> {
+        self.get(key)?.as_list()
+    }
+
+    pub fn get_str(&self, key: &str) -> Result<&str, NbtError> {
+        self.get(key)?.as_str()
+    }
+
+    pub fn get_bool(&self, key: &str) -> Result<bool, NbtError> {
+        self.get(key)?.try_into()
+    }
+
+    pub fn get_i8(&self, key: &str) -> Result<i8, NbtError> {
+        self.get(key)?.try_into()
+    }
+
+    pub fn get_i16(&self, key: &str) -> Result<i16, NbtError> {
+        self.get(key)?.try_into()
+    }
+
+    pub fn get_i32(&self, key: &str) -> Result<i32, NbtError> {
+        self.get(key)?.try_into()
+    }
+
+    pub fn get_i64(&self, key: &str) -> Result<i64, NbtError> {
+        self.get(key)?.try_into()
+    }
+
+    pub fn get_f32(&self, key: &str) -> Result<f32, NbtError> {
+        self.get(key)?.try_into()
+    }
+
+    pub fn get_f64(&self, key: &str) -> Result<f64, NbtError> {
+        self.get(key)?.try_into()
+    }
+}
+
+// every type that maps onto exactly one tag converts in both directions, the conversion back fails with the
+// tag that was found instead.
+macro_rules! value_conversions {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$ty> for Value {
+                fn from(value: $ty) -> Self {
+                    Value::$variant(value)
+                }
+            }
+
+            impl TryFrom<&Value> for $ty {
+                type Error = NbtError;
+
+                fn try_from(value: &Value) -> Result<Self, Self::Error> {
+                    match value {
+                        Value::$variant(value) => Ok(value.clone()),
+                        _ => Err(value.unexpected(stringify!($variant))),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+value_conversions! {
+    i8 => Byte,
+    i16 => Short,
+    i32 => Int,
+    i64 => Long,
+    f32 => Float,
+    f64 => Double,
+    Vec<i8> => ByteArray,
+    String => String,
+    Vec<Value> => List,
+    Compound => Compound,
+    Vec<i32> => IntArray,
+    Vec<i64> => LongArray,
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Byte(value as i8)
+    }
+}
+
+impl TryFrom<&Value> for bool {
+    type Error = NbtError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Byte(value) => Ok(*value != 0),
+            _ => Err(value.unexpected("Byte")),
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::String(value.to_owned())
+    }
+}
+
+impl TryFrom<&Value> for Value {
+    type Error = NbtError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        Ok(value.clone())
+    }
+}
+
+// vectors of anything that isn't backed by one of the array tags become lists.
+macro_rules! list_conversions {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl From<Vec<$ty>> for Value {
+                fn from(values: Vec<$ty>) -> Self {
+                    Value::List(values.into_iter().map(Value::from).collect())
+                }
+            }
+        )*
+    };
+}
+
+list_conversions!(bool, i16, f32, f64, String, &str, Compound);
@@ -0,0 +1,250 @@
+use serde_json::{json, Map, Number};
+
+use super::{Compound, NbtError, Value};
+
+// JSON has no NaN or infinity, so the tagged format spells those out as strings.
+fn float_to_json(value: f64) -> serde_json::Value {
+    match Number::from_f64(value) {
+        Some(number) => serde_json::Value::Number(number),
+        None if value.is_nan() => json!("NaN"),
+        None if value > 0.0 => json!("Infinity"),
+        None => json!("-Infinity"),
+    }
+}
+
+fn float_from_json(json: &serde_json::Value) -> Result<f64, NbtError> {
+    match json {
+        serde_json::Value::Number(number) => number.as_f64().ok_or_else(|| invalid(json)),
+        serde_json::Value::String(value) => match value.as_str() {
+            "NaN" => Ok(f64::NAN),
+            "Infinity" => Ok(f64::INFINITY),
+            "-Infinity" => Ok(f64::NEG_INFINITY),
+            _ => Err(invalid(json)),
+        },
+        _ => Err(invalid(json)),
+    }
+}
+
+fn integer_from_json<T: TryFrom<i64>>(json: &serde_json::Value) -> Result<T, NbtError> {
+    json.as_i64()
+        .and_then(|value| T::try_from(value).ok())
+        .ok_or_else(|| invalid(json))
+}
+
+fn array_from_json<T, F>(json: &serde_json::Value, convert: F) -> Result<Vec<T>, NbtError>
+where
+    F: Fn(&serde_json::Value) -> Result<T, NbtError>,
+{
+    json.as_array()
+        .ok_or_else(|| invalid(json))?
+        .iter()
+        .map(convert)
+        .collect()
+}
+
+// every integer up to 2^53 in magnitude is an exact double, past it they start rounding.
+const MAX_EXACT_DOUBLE: u64 = 1 << 53;
+
+fn invalid(json: &serde_json::Value) -> NbtError {
+    NbtError::InvalidJson(json.to_string())
+}
+
+impl Value {
+    /// Lossless conversion, every value becomes `{"type": ..., "value": ...}` so the exact tag types survive.
+    pub fn to_tagged_json(&self) -> serde_json::Value {
+        let (ty, value) = match self {
+            Value::Byte(value) => ("byte", json!(value)),
+            Value::Short(value) => ("short", json!(value)),
+            Value::Int(value) => ("int", json!(value)),
+            Value::Long(value) => ("long", json!(value)),
+            Value::Float(value) => ("float", float_to_json(*value as f64)),
+            Value::Double(value) => ("double", float_to_json(*value)),
+            Value::ByteArray(values) => ("byte_array", json!(values)),
+            Value::String(value) => ("string", json!(value)),
+            Value::List(values) => ("list", values.iter().map(Value::to_tagged_json).collect()),
+            Value::Compound(compound) => (
+                "compound",
+                compound
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.to_tagged_json()))
+                    .collect::<Map<_, _>>()
+                    .into(),
+            ),
+            Value::IntArray(values) => ("int_array", json!(values)),
+            Value::LongArray(values) => ("long_array", json!(values)),
+        };
+
+        json!({ "type": ty, "value": value })
+    }
+
+    pub fn from_tagged_json(json: &serde_json::Value) -> Result<Self, NbtError> {
+        let ty = json
+            .get("type")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| invalid(json))?;
+        let value = json.get("value").ok_or_else(|| invalid(json))?;
+
+        Ok(match ty {
+            "byte" => Value::Byte(integer_from_json(value)?),
+            "short" => Value::Short(integer_from_json(value)?),
+            "int" => Value::Int(integer_from_json(value)?),
+            "long" => Value::Long(integer_from_json(value)?),
+            "float" => Value::Float(float_from_json(value)? as f32),
+            "double" => Value::Double(float_from_json(value)?),
+            "byte_array" => Value::ByteArray(array_from_json(value, integer_from_json)?),
+            "string" => Value::String(value.as_str().ok_or_else(|| invalid(value))?.to_owned()),
+            "list" => Value::List(array_from_json(value, Value::from_tagged_json)?),
+            "compound" => Value::Compound(
+                value
+                    .as_object()
+                    .ok_or_else(|| invalid(value))?
+                    .iter()
+                    .map(|(key, value)| Ok((key.clone(), Value::from_tagged_json(value)?)))
+                    .collect::<Result<Compound, NbtError>>()?,
+            ),
+            "int_array" => Value::IntArray(array_from_json(value, integer_from_json)?),
+            "long_array" => Value::LongArray(array_from_json(value, integer_from_json)?),
+            _ => Err(invalid(json))?,
+        })
+    }
+
+    /// Plain JSON for people to read. The exact number types are lost, and NaN or infinite floats become null.
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            Value::Byte(value) => json!(value),
+            Value::Short(value) => json!(value),
+            Value::Int(value) => json!(value),
+            Value::Long(value) => json!(value),
+            Value::Float(value) => json!(value),
+            Value::Double(value) => json!(value),
+            Value::ByteArray(values) => json!(values),
+            Value::String(value) => json!(value),
+            Value::List(values) => values.iter().map(Value::to_json).collect(),
+            Value::Compound(compound) => compound
+                .iter()
+                .map(|(key, value)| (key.clone(), value.to_json()))
+                .collect::<Map<_, _>>()
+                .into(),
+            Value::IntArray(values) => json!(values),
+            Value::LongArray(values) => json!(values),
+        }
+    }
+
+    /// Guesses tag types for plain JSON: booleans become bytes, integers become ints (or longs when they don't
+    /// fit), other numbers become doubles and arrays become lists. Numbers in one array are widened to a common type,
+    /// unless a long in it can't be a double without rounding.
+    pub fn from_json(json: &serde_json::Value) -> Result<Self, NbtError> {
+        Ok(match json {
+            serde_json::Value::Null => Err(invalid(json))?,
+            serde_json::Value::Bool(value) => Value::from(*value),
+            serde_json::Value::Number(number) => match number.as_i64() {
+                Some(value) => match i32::try_from(value) {
+                    Ok(value) => Value::Int(value),
+                    Err(_) => Value::Long(value),
+                },
+                // integers past i64 would only survive as a rounded double.
+                None if number.is_u64() => Err(invalid(json))?,
+                None => Value::Double(number.as_f64().ok_or_else(|| invalid(json))?),
+            },
+            serde_json::Value::String(value) => Value::String(value.clone()),
+            serde_json::Value::Array(values) => {
+                let mut list = values
+                    .iter()
+                    .map(Value::from_json)
+                    .collect::<Result<Vec<Value>, NbtError>>()?;
+
+                if list.iter().any(|value| value.tag_id() != list[0].tag_id()) {
+                    let widest = list.iter().map(Value::tag_id).max().unwrap_or(0);
+
+                    for value in list.iter_mut() {
+                        *value = match (widest, &*value) {
+                            (3, Value::Byte(value)) => Value::Int(*value as i32),
+                            (4, Value::Byte(value)) => Value::Long(*value as i64),
+                            (4, Value::Int(value)) => Value::Long(*value as i64),
+                            (6, Value::Byte(value)) => Value::Double(*value as f64),
+                            (6, Value::Int(value)) => Value::Double(*value as f64),
+                            (6, Value::Long(value)) if value.unsigned_abs() <= MAX_EXACT_DOUBLE => {
+                                Value::Double(*value as f64)
+                            }
+                            (_, value) if value.tag_id() == widest => continue,
+                            _ => Err(invalid(json))?,
+                        };
+                    }
+                }
+
+                Value::List(list)
+            }
+            serde_json::Value::Object(object) => Value::Compound(
+                object
+                    .iter()
+                    .map(|(key, value)| Ok((key.clone(), Value::from_json(value)?)))
+                    .collect::<Result<Compound, NbtError>>()?,
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{nbt, packets::Encodable};
+
+    use super::*;
+
+    #[test]
+    fn tagged_json_is_lossless() {
+        let value = nbt!({
+            "z": 1i8,
+            "floats": [1.5f32, f32::NAN],
+            "long": i64::MAX,
+            "inner": { "s": "x", "ints": vec![1i32] },
+            "double": f64::INFINITY,
+        });
+
+        let json = value.to_tagged_json();
+        let decoded = Value::from_tagged_json(&json).unwrap();
+
+        // NaN isn't equal to itself, the encoded bytes are.
+        let (mut expected, mut actual) = (Vec::new(), Vec::new());
+        value.encode(&mut expected).unwrap();
+        decoded.encode(&mut actual).unwrap();
+        assert_eq!(actual, expected);
+
+        let text = serde_json::to_string(&json).unwrap();
+        assert!(text.find("\"z\"").unwrap() < text.find("\"floats\"").unwrap());
+        assert!(Value::from_tagged_json(&json!({ "type": "byte", "value": 300 })).is_err());
+    }
+
+    #[test]
+    fn plain_json_guesses_the_types() {
+        let json = nbt!({ "b": true, "l": [1i32, 5_000_000_000i64], "c": { "s": "x" } }).to_json();
+        assert_eq!(
+            json,
+            json!({ "b": 1, "l": [1, 5_000_000_000i64], "c": { "s": "x" } })
+        );
+
+        let value = Value::from_json(&json).unwrap();
+        assert_eq!(value.get("b").unwrap(), &Value::Int(1));
+        assert_eq!(
+            value.get("l").unwrap(),
+            &Value::List(vec![Value::Long(1), Value::Long(5_000_000_000)])
+        );
+        assert_eq!(
+            Value::from_json(&json!([1, 2.5])).unwrap(),
+            Value::List(vec![Value::Double(1.0), Value::Double(2.5)])
+        );
+
+        assert_eq!(
+            Value::from_json(&json!(i64::MAX)).unwrap(),
+            Value::Long(i64::MAX)
+        );
+        assert_eq!(
+            Value::from_json(&json!([1u64 << 53, 0.5])).unwrap(),
+            Value::List(vec![Value::Double(9007199254740992.0), Value::Double(0.5)])
+        );
+        assert!(Value::from_json(&json!([(1i64 << 53) + 1, 0.5])).is_err());
+        assert!(Value::from_json(&json!(u64::MAX)).is_err());
+
+        assert!(Value::from_json(&json!([1, "a"])).is_err());
+        assert!(Value::from_json(&json!(null)).is_err());
+    }
+}
@@ -0,0 +1,631 @@
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+use types::packet::ProtocolVersion;
+
+use crate::packets::{Decodable, Encodable, VersionedDecodable, VersionedEncodable};
+
+mod access;
+mod json;
+
+pub use access::NbtError;
+
+// vanilla refuses anything nested deeper than this, so do we. it also keeps malformed input from overflowing the stack.
+const MAX_DEPTH: usize = 512;
+
+// compounds keep their insertion order, so decoding and re-encoding a tree gives back the exact same bytes.
+// comparing two compounds still ignores the order of their keys.
+pub type Compound = IndexMap<String, Value>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Value {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    List(Vec<Value>),
+    Compound(Compound),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+impl Value {
+    pub fn tag_id(&self) -> u8 {
+        match self {
+            Value::Byte(_) => 1,
+            Value::Short(_) => 2,
+            Value::Int(_) => 3,
+            Value::Long(_) => 4,
+            Value::Float(_) => 5,
+            Value::Double(_) => 6,
+            Value::ByteArray(_) => 7,
+            Value::String(_) => 8,
+            Value::List(_) => 9,
+            Value::Compound(_) => 10,
+            Value::IntArray(_) => 11,
+            Value::LongArray(_) => 12,
+        }
+    }
+
+    /// Reads a named root tag, as found in files and in packets before 1.20.2.
+    pub fn decode_named<R: Read>(reader: &mut R) -> Result<(String, Self), std::io::Error> {
+        let tag_id = reader.read_u8()?;
+
+        if tag_id == 0 {
+            return Err(unexpected_end());
+        }
+
+        let name = read_string(reader)?;
+        let value = Self::decode_payload(tag_id, reader, 0)?;
+
+        Ok((name, value))
+    }
+
+    /// Writes a named root tag, as found in files and in packets before 1.20.2.
+    pub fn encode_named<W: Write>(&self, name: &str, writer: &mut W) -> Result<(), std::io::Error> {
+        writer.write_u8(self.tag_id())?;
+        write_string(name, writer)?;
+        self.encode_payload(writer)
+    }
+
+    fn decode_payload<R: Read>(
+        tag_id: u8,
+        reader: &mut R,
+        depth: usize,
+    ) -> Result<Self, std::io::Error> {
+        if depth > MAX_DEPTH {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "NBT is nested too deeply.",
+            ));
+        }
+
+        Ok(match tag_id {
+            1 => Value::Byte(reader.read_i8()?),
+            2 => Value::Short(reader.read_i16::<BigEndian>()?),
+            3 => Value::Int(reader.read_i32::<BigEndian>()?),
+            4 => Value::Long(reader.read_i64::<BigEndian>()?),
+            5 => Value::Float(reader.read_f32::<BigEndian>()?),
+            6 => Value::Double(reader.read_f64::<BigEndian>()?),
+            // the length comes from the network, so the capacity is capped like the list's.
+            7 => {
+                let len = read_length(reader)?;
+                let mut array = Vec::with_capacity(len.min(1024));
+
+                for _ in 0..len {
+                    array.push(reader.read_i8()?);
+                }
+
+                Value::ByteArray(array)
+            }
+            8 => Value::String(read_string(reader)?),
+            9 => {
+                let element_id = reader.read_u8()?;
+                let len = read_length(reader)?;
+
+                if element_id == 0 && len > 0 {
+                    return Err(unexpected_end());
+                }
+
+                let mut list = Vec::with_capacity(len.min(1024));
+
+                for _ in 0..len {
+                    list.push(Self::decode_payload(element_id, reader, depth + 1)?);
+                }
+
+                Value::List(list)
+            }
+            10 => {
+                let mut compound = Compound::new();
+
+                loop {
+                    let tag_id = reader.read_u8()?;
+
+                    if tag_id == 0 {
+                        break;
+                    }
+
+                    let name = read_string(reader)?;
+                    let value = Self::decode_payload(tag_id, reader, depth + 1)?;
+
+                    compound.insert(name, value);
+                }
+
+                Value::Compound(compound)
+            }
+            11 => {
+                let len = read_length(reader)?;
+                let mut array = Vec::with_capacity(len.min(1024));
+
+                for _ in 0..len {
+                    array.push(reader.read_i32::<BigEndian>()?);
+                }
+
+                Value::IntArray(array)
+            }
+            12 => {
+                let len = read_length(reader)?;
+                let mut array = Vec::with_capacity(len.min(1024));
+
+                for _ in 0..len {
+                    array.push(reader.read_i64::<BigEndian>()?);
+                }
+
+                Value::LongArray(array)
+            }
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Unsupported NBT tag id, {}", tag_id),
+            ))?,
+        })
+    }
+
+    fn encode_payload<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        match self {
+            Value::Byte(value) => writer.write_i8(*value),
+            Value::Short(value) => writer.write_i16::<BigEndian>(*value),
+            Value::Int(value) => writer.write_i32::<BigEndian>(*value),
+            Value::Long(value) => writer.write_i64::<BigEndian>(*value),
+            Value::Float(value) => writer.write_f32::<BigEndian>(*value),
+            Value::Double(value) => writer.write_f64::<BigEndian>(*value),
+            Value::ByteArray(values) => {
+                write_length(values.len(), writer)?;
+                values.iter().try_for_each(|value| writer.write_i8(*value))
+            }
+            Value::String(value) => write_string(value, writer),
+            Value::List(values) => {
+                // an empty list has no element type, vanilla writes TAG_End in that case as well.
+                let element_id = values.first().map(Value::tag_id).unwrap_or(0);
+
+                if values.iter().any(|value| value.tag_id() != element_id) {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "NBT lists may only contain values of a single type.",
+                    ));
+                }
+
+                writer.write_u8(element_id)?;
+                write_length(values.len(), writer)?;
+                values
+                    .iter()
+                    .try_for_each(|value| value.encode_payload(writer))
+            }
+            Value::Compound(compound) => {
+                for (name, value) in compound {
+                    writer.write_u8(value.tag_id())?;
+                    write_string(name, writer)?;
+                    value.encode_payload(writer)?;
+                }
+
+                writer.write_u8(0)
+            }
+            Value::IntArray(values) => {
+                write_length(values.len(), writer)?;
+                values
+                    .iter()
+                    .try_for_each(|value| writer.write_i32::<BigEndian>(*value))
+            }
+            Value::LongArray(values) => {
+                write_length(values.len(), writer)?;
+                values
+                    .iter()
+                    .try_for_each(|value| writer.write_i64::<BigEndian>(*value))
+            }
+        }
+    }
+}
+
+// since 1.20.2 the root tag sent over the network no longer has a name, this is the format used by packets.
+impl Decodable for Value {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let tag_id = reader.read_u8()?;
+
+        if tag_id == 0 {
+            return Err(unexpected_end());
+        }
+
+        Self::decode_payload(tag_id, reader, 0)
+    }
+}
+
+impl Encodable for Value {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        writer.write_u8(self.tag_id())?;
+        self.encode_payload(writer)
+    }
+}
+
+impl VersionedDecodable for Value {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        if version < ProtocolVersion::V1_20_2 {
+            return Ok(Self::decode_named(reader)?.1);
+        }
+
+        Self::decode(reader)
+    }
+}
+
+impl VersionedEncodable for Value {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        if version < ProtocolVersion::V1_20_2 {
+            return self.encode_named("", writer);
+        }
+
+        self.encode(writer)
+    }
+}
+
+// a root tag that can also be a lone TAG_End when there is no data, e.g. for block entities.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OptionalValue(pub Option<Value>);
+
+impl VersionedDecodable for OptionalValue {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        let tag_id = reader.read_u8()?;
+
+        if tag_id == 0 {
+            return Ok(OptionalValue(None));
+        }
+
+        if version < ProtocolVersion::V1_20_2 {
+            read_string(reader)?;
+        }
+
+        Ok(OptionalValue(Some(Value::decode_payload(
+            tag_id, reader, 0,
+        )?)))
+    }
+}
+
+impl VersionedEncodable for OptionalValue {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        match &self.0 {
+            Some(value) => value.encode_versioned(writer, version),
+            None => writer.write_u8(0),
+        }
+    }
+}
+
+/// Builds a `Value` tree, e.g. `nbt!{ "name": "Steve", "pos": [1.0, 64.0, 1.0], "data": { "level": 3 } }`.
+/// Braces become compounds, brackets become lists and anything else goes through `Value::from`.
+#[macro_export]
+macro_rules! nbt {
+    (@compound $compound:ident) => {};
+    (@compound $compound:ident $key:literal : { $($inner:tt)* } $(, $($rest:tt)*)?) => {
+        $compound.insert(::std::string::String::from($key), $crate::nbt!({ $($inner)* }));
+        $crate::nbt!(@compound $compound $($($rest)*)?);
+    };
+    (@compound $compound:ident $key:literal : [ $($inner:tt)* ] $(, $($rest:tt)*)?) => {
+        $compound.insert(::std::string::String::from($key), $crate::nbt!([ $($inner)* ]));
+        $crate::nbt!(@compound $compound $($($rest)*)?);
+    };
+    (@compound $compound:ident $key:literal : $value:expr $(, $($rest:tt)*)?) => {
+        $compound.insert(::std::string::String::from($key), $crate::nbt::Value::from($value));
+        $crate::nbt!(@compound $compound $($($rest)*)?);
+    };
+
+    (@list [$($items:expr),*]) => {
+        $crate::nbt::Value::List(::std::vec![$($items),*])
+    };
+    (@list [$($items:expr),*] { $($inner:tt)* } $(, $($rest:tt)*)?) => {
+        $crate::nbt!(@list [$($items,)* $crate::nbt!({ $($inner)* })] $($($rest)*)?)
+    };
+    (@list [$($items:expr),*] [ $($inner:tt)* ] $(, $($rest:tt)*)?) => {
+        $crate::nbt!(@list [$($items,)* $crate::nbt!([ $($inner)* ])] $($($rest)*)?)
+    };
+    (@list [$($items:expr),*] $value:expr $(, $($rest:tt)*)?) => {
+        $crate::nbt!(@list [$($items,)* $crate::nbt::Value::from($value)] $($($rest)*)?)
+    };
+
+    () => {
+        $crate::nbt::Value::Compound($crate::nbt::Compound::new())
+    };
+    ({ $($inner:tt)* }) => {{
+        #[allow(unused_mut)]
+        let mut compound = $crate::nbt::Compound::new();
+        $crate::nbt!(@compound compound $($inner)*);
+        $crate::nbt::Value::Compound(compound)
+    }};
+    ([ $($inner:tt)* ]) => {
+        $crate::nbt!(@list [] $($inner)*)
+    };
+    ($key:literal : $($rest:tt)*) => {
+        $crate::nbt!({ $key : $($rest)* })
+    };
+    ($value:expr) => {
+        $crate::nbt::Value::from($value)
+    };
+}
+
+fn unexpected_end() -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        "Unexpected TAG_End in NBT.",
+    )
+}
+
+fn read_length<R: Read>(reader: &mut R) -> Result<usize, std::io::Error> {
+    let len = reader.read_i32::<BigEndian>()?;
+
+    len.try_into().map_err(|_| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Negative NBT length, {}", len),
+        )
+    })
+}
+
+fn write_length<W: Write>(len: usize, writer: &mut W) -> Result<(), std::io::Error> {
+    let len: i32 = len.try_into().map_err(|_| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, "NBT value is too long.")
+    })?;
+
+    writer.write_i32::<BigEndian>(len)
+}
+
+// NBT strings use java's modified UTF-8: the null character takes two bytes and characters outside of the
+// BMP are written as two three-byte surrogates. we go through UTF-16 so those round trip byte for byte.
+fn read_string<R: Read>(reader: &mut R) -> Result<String, std::io::Error> {
+    let len = reader.read_u16::<BigEndian>()?;
+    let mut buf = vec![0; len as usize];
+
+    reader.read_exact(&mut buf)?;
+
+    let invalid = || {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "NBT string is not valid modified UTF-8.",
+        )
+    };
+
+    let mut units = Vec::with_capacity(buf.len());
+    let mut bytes = buf.into_iter();
+
+    while let Some(first) = bytes.next() {
+        let mut continuation = || match bytes.next() {
+            Some(byte) if byte & 0b11000000 == 0b10000000 => Ok((byte & 0b00111111) as u16),
+            _ => Err(invalid()),
+        };
+
+        let unit = match first {
+            0x00..=0x7F => first as u16,
+            0xC0..=0xDF => ((first & 0b00011111) as u16) << 6 | continuation()?,
+            0xE0..=0xEF => {
+                ((first & 0b00001111) as u16) << 12 | continuation()? << 6 | continuation()?
+            }
+            _ => return Err(invalid()),
+        };
+
+        units.push(unit);
+    }
+
+    String::from_utf16(&units).map_err(|_| invalid())
+}
+
+fn write_string<W: Write>(value: &str, writer: &mut W) -> Result<(), std::io::Error> {
+    let mut buf = Vec::with_capacity(value.len());
+
+    for unit in value.encode_utf16() {
+        match unit {
+            0x0001..=0x007F => buf.push(unit as u8),
+            0x0000 | 0x0080..=0x07FF => {
+                buf.push(0b11000000 | (unit >> 6) as u8);
+                buf.push(0b10000000 | (unit & 0b00111111) as u8);
+            }
+            _ => {
+                buf.push(0b11100000 | (unit >> 12) as u8);
+                buf.push(0b10000000 | (unit >> 6 & 0b00111111) as u8);
+                buf.push(0b10000000 | (unit & 0b00111111) as u8);
+            }
+        }
+    }
+
+    let len: u16 = buf.len().try_into().map_err(|_| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, "NBT string is too long.")
+    })?;
+
+    writer.write_u16::<BigEndian>(len)?;
+    writer.write_all(&buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player() -> Value {
+        nbt!({
+            "name": "Steve",
+            "pos": [1.5f64, 64.0f64, -3.25f64],
+            "inventory": [{ "id": "minecraft:stone", "count": 3i8 }, {}],
+            "data": { "level": 3i32, "seed": i64::MIN },
+            "bytes": vec![1i8, -1],
+            "ints": vec![7i32],
+            "longs": vec![1i64, -2],
+            "empty": [],
+        })
+    }
+
+    #[test]
+    fn round_trip_keeps_the_bytes() {
+        let value = player();
+
+        let mut buf = Vec::new();
+        value.encode_named("root", &mut buf).unwrap();
+
+        let (name, decoded) = Value::decode_named(&mut &buf[..]).unwrap();
+        assert_eq!(name, "root");
+        assert_eq!(decoded, value);
+
+        let mut again = Vec::new();
+        decoded.encode_named("root", &mut again).unwrap();
+        assert_eq!(again, buf);
+    }
+
+    #[test]
+    fn network_root_is_nameless_since_1_20_2() {
+        let value = nbt!({ "a": 1i8 });
+        let nameless = [10, 1, 0, 1, b'a', 1, 0];
+
+        let mut buf = Vec::new();
+        value
+            .encode_versioned(&mut buf, ProtocolVersion::V1_20_2)
+            .unwrap();
+        assert_eq!(buf, nameless);
+        assert_eq!(
+            Value::decode_versioned(&mut &buf[..], ProtocolVersion::V1_20_2).unwrap(),
+            value
+        );
+
+        // an empty name before 1.20.2.
+        let mut buf = Vec::new();
+        value
+            .encode_versioned(&mut buf, ProtocolVersion::V1_20)
+            .unwrap();
+        assert_eq!(buf[..3], [10, 0, 0]);
+        assert_eq!(buf[3..], nameless[1..]);
+        assert_eq!(
+            Value::decode_versioned(&mut &buf[..], ProtocolVersion::V1_20).unwrap(),
+            value
+        );
+
+        // any tag can be the root, e.g. a string for text components.
+        let mut buf = Vec::new();
+        Value::String("hi".into()).encode(&mut buf).unwrap();
+        assert_eq!(buf, [8, 0, 2, b'h', b'i']);
+    }
+
+    #[test]
+    fn optional_value_can_be_a_lone_end_tag() {
+        for version in [ProtocolVersion::V1_19_3, ProtocolVersion::V1_21] {
+            let mut buf = Vec::new();
+            OptionalValue(None)
+                .encode_versioned(&mut buf, version)
+                .unwrap();
+            assert_eq!(buf, [0]);
+            assert_eq!(
+                OptionalValue::decode_versioned(&mut &buf[..], version).unwrap(),
+                OptionalValue(None)
+            );
+
+            let value = OptionalValue(Some(player()));
+            let mut buf = Vec::new();
+            value.encode_versioned(&mut buf, version).unwrap();
+            assert_eq!(
+                OptionalValue::decode_versioned(&mut &buf[..], version).unwrap(),
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_data() {
+        // lists of lists, ending in an empty list.
+        let nested = |depth| {
+            let mut buf = vec![9];
+            for _ in 0..depth {
+                buf.extend([9, 0, 0, 0, 1]);
+            }
+            buf.extend([0, 0, 0, 0, 0]);
+            buf
+        };
+        assert!(Value::decode(&mut &nested(MAX_DEPTH)[..]).is_ok());
+        let error = Value::decode(&mut &nested(MAX_DEPTH + 1)[..]).unwrap_err();
+        assert_eq!(error.to_string(), "NBT is nested too deeply.");
+
+        // a huge array length must fail on the missing data instead of allocating it.
+        assert!(Value::decode(&mut &[7u8, 0x7F, 0xFF, 0xFF, 0xFF, 1][..]).is_err());
+        assert!(Value::decode(&mut &[12u8, 0x7F, 0xFF, 0xFF, 0xFF][..]).is_err());
+
+        assert!(Value::decode(&mut &[0u8][..]).is_err());
+        assert!(Value::decode(&mut &[13u8][..]).is_err());
+        assert!(Value::List(vec![Value::Byte(1), Value::Int(1)])
+            .encode(&mut Vec::new())
+            .is_err());
+    }
+
+    #[test]
+    fn macro_builds_the_tree() {
+        let mut inventory = Compound::new();
+        inventory.insert("id".into(), Value::String("minecraft:stone".into()));
+        inventory.insert("count".into(), Value::Byte(3));
+
+        let value = player();
+        assert_eq!(
+            value.get("inventory").unwrap(),
+            &Value::List(vec![
+                Value::Compound(inventory),
+                Value::Compound(Compound::new())
+            ])
+        );
+        assert_eq!(value.get("bytes").unwrap(), &Value::ByteArray(vec![1, -1]));
+        assert_eq!(value.get("empty").unwrap(), &Value::List(Vec::new()));
+        assert_eq!(nbt!({}), Value::Compound(Compound::new()));
+        assert_eq!(nbt!(5i32), Value::Int(5));
+        assert_eq!(nbt!(true), Value::Byte(1));
+    }
+
+    #[test]
+    fn accessors() {
+        let value = player();
+
+        assert_eq!(value.get_str("name").unwrap(), "Steve");
+        assert_eq!(value.get_compound("data").unwrap().len(), 2);
+        assert_eq!(value.get_list("pos").unwrap().len(), 3);
+        assert_eq!(value.get("data").unwrap().get_i32("level").unwrap(), 3);
+        assert_eq!(
+            value.get("data").unwrap().get_i64("seed").unwrap(),
+            i64::MIN
+        );
+        assert_eq!(
+            value.get("pos").unwrap().as_list_of::<f64>().unwrap(),
+            [1.5, 64.0, -3.25]
+        );
+        assert!(nbt!({ "flag": true }).get_bool("flag").unwrap());
+
+        assert_eq!(
+            value.get_i32("missing"),
+            Err(NbtError::MissingKey("missing".into()))
+        );
+        assert_eq!(
+            value.get_i32("name"),
+            Err(NbtError::UnexpectedType {
+                expected: "Int",
+                found: "String"
+            })
+        );
+        assert_eq!(
+            value.get("pos").unwrap().as_list_of::<f32>(),
+            Err(NbtError::UnexpectedType {
+                expected: "Float",
+                found: "Double"
+            })
+        );
+        assert_eq!(
+            Value::Int(1).get("name"),
+            Err(NbtError::UnexpectedType {
+                expected: "Compound",
+                found: "Int"
+            })
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use proc_macros::MinecraftPacket;
+use uuid::Uuid;
+
+use crate::{
+    configuration::{KnownPack, RegistryEntry},
+    identifier::Identifier,
+    nbt,
+    tags::RegistryTags,
+    text::TextComponent,
+};
+
+// 0x03
+#[derive(MinecraftPacket, Debug, PartialEq)]
+pub struct FinishConfiguration {}
+
+// 0x07, before 1.20.5 all registries were sent at once as a single compound.
+#[derive(MinecraftPacket, Debug, PartialEq)]
+pub struct RegistryData {
+    #[until(V1_20_5)]
+    pub registry_codec: Option<nbt::Value>,
+    #[since(V1_20_5)]
+    pub registry_id: Option<Identifier>,
+    #[since(V1_20_5)]
+    pub entries: Option<Vec<RegistryEntry>>,
+}
+
+// 0x08, since 1.20.3. without a uuid every resource pack is removed.
+#[derive(MinecraftPacket, Debug, PartialEq)]
+pub struct RemoveResourcePack {
+    pub uuid: Option<Uuid>,
+}
+
+// 0x09
+#[derive(MinecraftPacket, Debug, PartialEq)]
+pub struct AddResourcePack {
+    #[since(V1_20_3)]
+    pub uuid: Option<Uuid>,
+    pub url: String,
+    pub hash: String,
+    pub forced: bool,
+    #[versioned]
+    pub prompt: Option<TextComponent>,
+}
+
+// 0x0C
+#[derive(MinecraftPacket, Debug, PartialEq)]
+pub struct FeatureFlags {
+    pub feature_flags: Vec<Identifier>,
+}
+
+// 0x0E, since 1.20.5
+#[derive(MinecraftPacket, Debug, PartialEq)]
+pub struct ClientboundKnownPacks {
+    pub known_packs: Vec<KnownPack>,
+}
+
+// 0x0D, since 1.20.2 the tags are sent before Play as well.
+#[derive(MinecraftPacket, Debug, PartialEq)]
+pub struct ConfigurationUpdateTags {
+    pub registries: Vec<RegistryTags>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use types::packet::ProtocolVersion;
+
+    use crate::{
+        packets::{VersionedDecodable, VersionedEncodable},
+        text::HoverEvent,
+    };
+
+    use super::*;
+
+    fn round_trip(packet: &AddResourcePack, version: ProtocolVersion) -> Vec<u8> {
+        let mut buf = Vec::new();
+        packet.encode_versioned(&mut buf, version).unwrap();
+
+        let mut reader = &buf[..];
+        assert_eq!(
+            &AddResourcePack::decode_versioned(&mut reader, version).unwrap(),
+            packet
+        );
+        assert!(reader.is_empty());
+
+        buf
+    }
+
+    #[test]
+    fn add_resource_pack_prompt() {
+        let mut hint = TextComponent::text("required");
+        hint.style.hover_event = Some(HoverEvent {
+            action: "show_text".into(),
+            contents: Some(json!({ "text": "the server needs this pack" })),
+            value: None,
+        });
+
+        let mut prompt = TextComponent::text("Download the pack, it is ");
+        prompt.extra.push(hint);
+
+        // until 1.20.3 there is no uuid and the prompt is JSON.
+        let packet = AddResourcePack {
+            uuid: None,
+            url: "a".into(),
+            hash: "b".into(),
+            forced: true,
+            prompt: Some(prompt.clone()),
+        };
+        let buf = round_trip(&packet, ProtocolVersion::V1_20_2);
+        assert_eq!(buf[..6], [1, b'a', 1, b'b', 1, 1]);
+        assert!(buf.ends_with(b"}"));
+
+        // since then the uuid comes first and the prompt is NBT.
+        let packet = AddResourcePack {
+            uuid: Some(Uuid::from_bytes([7; 16])),
+            ..packet
+        };
+        for version in [ProtocolVersion::V1_20_3, ProtocolVersion::V1_21] {
+            let buf = round_trip(&packet, version);
+            assert_eq!(buf[..16], [7; 16]);
+            assert_eq!(buf[21..23], [1, 10]);
+        }
+    }
+}
@@ -1 +1,131 @@
+use proc_macros::MinecraftPacket;
+use uuid::Uuid;
 
+use crate::{
+    datatypes::remaining::RemainingBytes, identifier::Identifier, profile::ProfileProperty,
+    text::TextComponent, VarInt,
+};
+
+// 0x00
+#[derive(MinecraftPacket, Debug, PartialEq)]
+pub struct LoginDisconnect {
+    pub reason: TextComponent,
+}
+
+// 0x01
+#[derive(MinecraftPacket, Debug, PartialEq)]
+pub struct EncryptionRequest {
+    pub server_id: String,
+    pub public_key: Vec<u8>,
+    pub verify_token: Vec<u8>,
+    #[since(V1_20_5)]
+    pub should_authenticate: Option<bool>,
+}
+
+// 0x02
+#[derive(MinecraftPacket, Debug, PartialEq)]
+pub struct LoginSuccess {
+    pub uuid: Uuid,
+    pub username: String,
+    pub properties: Vec<ProfileProperty>,
+    #[since(V1_20_5)]
+    #[until(V1_21_2)]
+    pub strict_error_handling: Option<bool>,
+}
+
+// 0x03
+#[derive(MinecraftPacket, Debug, PartialEq)]
+pub struct SetCompression {
+    pub threshold: VarInt,
+}
+
+// 0x04
+#[derive(MinecraftPacket, Debug, PartialEq)]
+pub struct LoginPluginRequest {
+    pub message_id: VarInt,
+    pub channel: Identifier,
+    pub data: RemainingBytes,
+}
+
+// 0x05, since 1.20.5
+#[derive(MinecraftPacket, Debug, PartialEq)]
+pub struct CookieRequest {
+    pub key: Identifier,
+}
+
+#[cfg(test)]
+mod tests {
+    use types::packet::ProtocolVersion;
+
+    use crate::packets::{registry::Packet as _, VersionedDecodable, VersionedEncodable};
+
+    use super::*;
+
+    fn round_trip<T>(packet: &T, version: ProtocolVersion) -> Vec<u8>
+    where
+        T: VersionedEncodable + VersionedDecodable + PartialEq + std::fmt::Debug,
+    {
+        let mut buf = Vec::new();
+        packet.encode_versioned(&mut buf, version).unwrap();
+
+        let mut reader = &buf[..];
+        assert_eq!(&T::decode_versioned(&mut reader, version).unwrap(), packet);
+        assert!(reader.is_empty());
+
+        buf
+    }
+
+    #[test]
+    fn login_disconnect_is_always_json() {
+        let packet = LoginDisconnect {
+            reason: TextComponent::text("bye"),
+        };
+
+        for version in [
+            ProtocolVersion::V1_19,
+            ProtocolVersion::V1_20_3,
+            ProtocolVersion::V1_21_2,
+        ] {
+            assert_eq!(round_trip(&packet, version), b"\x0E{\"text\":\"bye\"}");
+        }
+    }
+
+    #[test]
+    fn login_success_layouts() {
+        let packet = |strict_error_handling| LoginSuccess {
+            uuid: Uuid::from_bytes([7; 16]),
+            username: "a".into(),
+            properties: vec![ProfileProperty {
+                name: "textures".into(),
+                value: "b".into(),
+                signature: None,
+            }],
+            strict_error_handling,
+        };
+
+        for version in [ProtocolVersion::V1_19, ProtocolVersion::V1_20_3] {
+            let buf = round_trip(&packet(None), version);
+            assert_eq!(buf[16..], *b"\x01a\x01\x08textures\x01b\x00");
+        }
+
+        // strict error handling was only sent by 1.20.5 and 1.21.
+        for version in [ProtocolVersion::V1_20_5, ProtocolVersion::V1_21] {
+            assert_eq!(round_trip(&packet(Some(true)), version).last(), Some(&1));
+            assert!(packet(None)
+                .encode_versioned(&mut Vec::new(), version)
+                .is_err());
+        }
+
+        assert_eq!(
+            round_trip(&packet(None), ProtocolVersion::V1_21_2).last(),
+            Some(&0)
+        );
+    }
+
+    #[test]
+    fn cookie_request_was_added_in_1_20_5() {
+        assert_eq!(CookieRequest::ID, 0x05);
+        assert_eq!(CookieRequest::id_for(ProtocolVersion::V1_20_3), None);
+        assert_eq!(CookieRequest::id_for(ProtocolVersion::V1_20_5), Some(0x05));
+    }
+}
@@ -1,15 +1,36 @@
+use std::io::{Read, Write};
+
 use proc_macros::MinecraftPacket;
+use types::packet::ProtocolVersion;
 use uuid::Uuid;
 
 use crate::{
-    animation::EntityAnimationType, datatypes::sized::SizedVec, position::Angle,
-    statistics::Statistic, VarInt,
+    animation::EntityAnimationType,
+    boss_bar::BossBarAction,
+    chat::{ChatTypeReference, FilterType, MessageSignature, PreviousMessage},
+    chunk::{ChunkData, LightData, SectionBlock},
+    commands::CommandNode,
+    datatypes::sized::SizedVec,
+    identifier::Identifier,
+    nbt::{self, OptionalValue},
+    packets::{required, Decodable, Encodable, VersionedDecodable, VersionedEncodable},
+    particle::ParticleData,
+    player_info::{LegacyPlayerInfoAction, PlayerInfoActions, PlayerInfoEntry},
+    position::{Angle, Position, PositionDelta, SectionPosition},
+    recipe::Recipe,
+    scoreboard::{DisplaySlot, NumberFormat, ObjectiveAction, TeamAction},
+    slot::Slot,
+    sound::{SoundCategory, SoundReference},
+    spawn::{DeathLocation, DimensionType, SpawnInfo},
+    statistics::Statistic,
+    tags::RegistryTags,
+    text::TextComponent,
+    VarInt,
 };
 
 // 0x00
 #[derive(MinecraftPacket, Debug, PartialEq)]
 pub struct SpawnEntity {
-    pub id: VarInt,
     pub entity_id: VarInt,
     pub entity_unique_id: Uuid,
     pub ty: VarInt,
@@ -28,7 +49,6 @@ pub struct SpawnEntity {
 // 0x01
 #[derive(MinecraftPacket, Debug, PartialEq)]
 pub struct SpawnExperienceOrb {
-    pub id: VarInt,
     pub entity_id: VarInt,
     pub x: f64,
     pub y: f64,
@@ -39,7 +59,6 @@ pub struct SpawnExperienceOrb {
 // 0x02
 #[derive(MinecraftPacket, Debug, PartialEq)]
 pub struct SpawnPlayer {
-    pub id: VarInt,
     pub entity_id: VarInt,
     pub unique_id: Uuid,
     pub x: f64,
@@ -52,7 +71,6 @@ pub struct SpawnPlayer {
 // 0x03
 #[derive(MinecraftPacket, Debug, PartialEq)]
 pub struct EntityAnimation {
-    pub id: VarInt,
     pub entity_id: VarInt,
     pub animation: EntityAnimationType,
 }
@@ -60,7 +78,1529 @@ pub struct EntityAnimation {
 // 0x04
 #[derive(MinecraftPacket, Debug, PartialEq, Clone)]
 pub struct AwardStatistics {
-    pub id: VarInt,
     pub count: VarInt,
     pub statistic: SizedVec<Statistic, 1>,
 }
+
+// 0x20
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct ChunkDataAndUpdateLight {
+    pub chunk_x: i32,
+    pub chunk_z: i32,
+    #[versioned]
+    pub chunk: ChunkData,
+    #[versioned]
+    pub light: LightData,
+}
+
+// 0x23
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct UpdateLight {
+    pub chunk_x: VarInt,
+    pub chunk_z: VarInt,
+    #[versioned]
+    pub light: LightData,
+}
+
+// 0x17
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct Disconnect {
+    #[versioned]
+    pub reason: TextComponent,
+}
+
+// 0x1F
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct ClientboundKeepAlive {
+    pub keep_alive_id: i64,
+}
+
+// 0x24, also known as Login (play). before 1.20.2 the spawn info was spread over the packet and the registries were
+// sent here instead of in the configuration state.
+#[derive(Debug, PartialEq, Clone)]
+pub struct JoinGame {
+    pub entity_id: i32,
+    pub is_hardcore: bool,
+    pub dimension_names: Vec<Identifier>,
+    // until 1.20.2
+    pub registry_codec: Option<nbt::Value>,
+    pub max_players: VarInt,
+    pub view_distance: VarInt,
+    pub simulation_distance: VarInt,
+    pub reduced_debug_info: bool,
+    pub enable_respawn_screen: bool,
+    // since 1.20.2
+    pub do_limited_crafting: Option<bool>,
+    pub spawn_info: SpawnInfo,
+    // since 1.20.5
+    pub enforces_secure_chat: Option<bool>,
+}
+
+impl VersionedDecodable for JoinGame {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        let entity_id = i32::decode(reader)?;
+        let is_hardcore = bool::decode(reader)?;
+
+        if version >= ProtocolVersion::V1_20_2 {
+            return Ok(JoinGame {
+                entity_id,
+                is_hardcore,
+                dimension_names: Vec::decode(reader)?,
+                registry_codec: None,
+                max_players: VarInt::decode(reader)?,
+                view_distance: VarInt::decode(reader)?,
+                simulation_distance: VarInt::decode(reader)?,
+                reduced_debug_info: bool::decode(reader)?,
+                enable_respawn_screen: bool::decode(reader)?,
+                do_limited_crafting: Some(bool::decode(reader)?),
+                spawn_info: SpawnInfo::decode_versioned(reader, version)?,
+                enforces_secure_chat: if version >= ProtocolVersion::V1_20_5 {
+                    Some(bool::decode(reader)?)
+                } else {
+                    None
+                },
+            });
+        }
+
+        let game_mode = u8::decode(reader)?;
+        let previous_game_mode = i8::decode(reader)?;
+        let dimension_names = Vec::decode(reader)?;
+        let registry_codec = nbt::Value::decode_versioned(reader, version)?;
+        let dimension_type = DimensionType::decode_versioned(reader, version)?;
+        let dimension_name = Identifier::decode(reader)?;
+        let hashed_seed = i64::decode(reader)?;
+        let max_players = VarInt::decode(reader)?;
+        let view_distance = VarInt::decode(reader)?;
+        let simulation_distance = VarInt::decode(reader)?;
+        let reduced_debug_info = bool::decode(reader)?;
+        let enable_respawn_screen = bool::decode(reader)?;
+        let is_debug = bool::decode(reader)?;
+        let is_flat = bool::decode(reader)?;
+        let death_location = Option::<DeathLocation>::decode(reader)?;
+
+        let portal_cooldown = if version >= ProtocolVersion::V1_20 {
+            Some(VarInt::decode(reader)?)
+        } else {
+            None
+        };
+
+        Ok(JoinGame {
+            entity_id,
+            is_hardcore,
+            dimension_names,
+            registry_codec: Some(registry_codec),
+            max_players,
+            view_distance,
+            simulation_distance,
+            reduced_debug_info,
+            enable_respawn_screen,
+            do_limited_crafting: None,
+            spawn_info: SpawnInfo {
+                dimension_type,
+                dimension_name,
+                hashed_seed,
+                game_mode,
+                previous_game_mode,
+                is_debug,
+                is_flat,
+                death_location,
+                portal_cooldown,
+            },
+            enforces_secure_chat: None,
+        })
+    }
+}
+
+impl VersionedEncodable for JoinGame {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        self.entity_id.encode(writer)?;
+        self.is_hardcore.encode(writer)?;
+
+        if version >= ProtocolVersion::V1_20_2 {
+            self.dimension_names.encode(writer)?;
+            self.max_players.encode(writer)?;
+            self.view_distance.encode(writer)?;
+            self.simulation_distance.encode(writer)?;
+            self.reduced_debug_info.encode(writer)?;
+            self.enable_respawn_screen.encode(writer)?;
+            required(&self.do_limited_crafting, "do_limited_crafting")?.encode(writer)?;
+            self.spawn_info.encode_versioned(writer, version)?;
+
+            if version >= ProtocolVersion::V1_20_5 {
+                required(&self.enforces_secure_chat, "enforces_secure_chat")?.encode(writer)?;
+            }
+
+            return Ok(());
+        }
+
+        let spawn_info = &self.spawn_info;
+
+        spawn_info.game_mode.encode(writer)?;
+        spawn_info.previous_game_mode.encode(writer)?;
+        self.dimension_names.encode(writer)?;
+        required(&self.registry_codec, "registry_codec")?.encode_versioned(writer, version)?;
+        spawn_info
+            .dimension_type
+            .encode_versioned(writer, version)?;
+        spawn_info.dimension_name.encode(writer)?;
+        spawn_info.hashed_seed.encode(writer)?;
+        self.max_players.encode(writer)?;
+        self.view_distance.encode(writer)?;
+        self.simulation_distance.encode(writer)?;
+        self.reduced_debug_info.encode(writer)?;
+        self.enable_respawn_screen.encode(writer)?;
+        spawn_info.is_debug.encode(writer)?;
+        spawn_info.is_flat.encode(writer)?;
+        spawn_info.death_location.encode(writer)?;
+
+        if version >= ProtocolVersion::V1_20 {
+            required(&spawn_info.portal_cooldown, "portal_cooldown")?.encode(writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Decodable for JoinGame {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        Self::decode_versioned(reader, ProtocolVersion::LATEST)
+    }
+}
+
+impl Encodable for JoinGame {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        self.encode_versioned(writer, ProtocolVersion::LATEST)
+    }
+}
+
+// 0x2E
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct Ping {
+    pub id: i32,
+}
+
+pub const KEEP_ATTRIBUTES: u8 = 0x01;
+pub const KEEP_METADATA: u8 = 0x02;
+
+// 0x3D. before 1.20 there was a single flag to keep everything, and before 1.20.2 it was sent in the middle of the
+// spawn info.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Respawn {
+    pub spawn_info: SpawnInfo,
+    pub data_kept: u8,
+}
+
+impl VersionedDecodable for Respawn {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        if version >= ProtocolVersion::V1_20_2 {
+            return Ok(Respawn {
+                spawn_info: SpawnInfo::decode_versioned(reader, version)?,
+                data_kept: u8::decode(reader)?,
+            });
+        }
+
+        let dimension_type = DimensionType::decode_versioned(reader, version)?;
+        let dimension_name = Identifier::decode(reader)?;
+        let hashed_seed = i64::decode(reader)?;
+        let game_mode = u8::decode(reader)?;
+        let previous_game_mode = i8::decode(reader)?;
+        let is_debug = bool::decode(reader)?;
+        let is_flat = bool::decode(reader)?;
+
+        let data_kept = match version >= ProtocolVersion::V1_20 {
+            true => u8::decode(reader)?,
+            false if bool::decode(reader)? => KEEP_ATTRIBUTES | KEEP_METADATA,
+            false => 0,
+        };
+
+        let death_location = Option::<DeathLocation>::decode(reader)?;
+
+        let portal_cooldown = if version >= ProtocolVersion::V1_20 {
+            Some(VarInt::decode(reader)?)
+        } else {
+            None
+        };
+
+        Ok(Respawn {
+            spawn_info: SpawnInfo {
+                dimension_type,
+                dimension_name,
+                hashed_seed,
+                game_mode,
+                previous_game_mode,
+                is_debug,
+                is_flat,
+                death_location,
+                portal_cooldown,
+            },
+            data_kept,
+        })
+    }
+}
+
+impl VersionedEncodable for Respawn {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        let spawn_info = &self.spawn_info;
+
+        if version >= ProtocolVersion::V1_20_2 {
+            spawn_info.encode_versioned(writer, version)?;
+            return self.data_kept.encode(writer);
+        }
+
+        spawn_info
+            .dimension_type
+            .encode_versioned(writer, version)?;
+        spawn_info.dimension_name.encode(writer)?;
+        spawn_info.hashed_seed.encode(writer)?;
+        spawn_info.game_mode.encode(writer)?;
+        spawn_info.previous_game_mode.encode(writer)?;
+        spawn_info.is_debug.encode(writer)?;
+        spawn_info.is_flat.encode(writer)?;
+
+        if version >= ProtocolVersion::V1_20 {
+            self.data_kept.encode(writer)?;
+        } else {
+            (self.data_kept != 0).encode(writer)?;
+        }
+
+        spawn_info.death_location.encode(writer)?;
+
+        if version >= ProtocolVersion::V1_20 {
+            required(&spawn_info.portal_cooldown, "portal_cooldown")?.encode(writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Decodable for Respawn {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        Self::decode_versioned(reader, ProtocolVersion::LATEST)
+    }
+}
+
+impl Encodable for Respawn {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        self.encode_versioned(writer, ProtocolVersion::LATEST)
+    }
+}
+
+// 0x27
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct UpdateEntityPosition {
+    pub entity_id: VarInt,
+    pub delta_x: PositionDelta,
+    pub delta_y: PositionDelta,
+    pub delta_z: PositionDelta,
+    pub on_ground: bool,
+}
+
+// 0x28
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct UpdateEntityPositionAndRotation {
+    pub entity_id: VarInt,
+    pub delta_x: PositionDelta,
+    pub delta_y: PositionDelta,
+    pub delta_z: PositionDelta,
+    pub yaw: Angle,
+    pub pitch: Angle,
+    pub on_ground: bool,
+}
+
+// 0x29
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct UpdateEntityRotation {
+    pub entity_id: VarInt,
+    pub yaw: Angle,
+    pub pitch: Angle,
+    pub on_ground: bool,
+}
+
+// 0x3A
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct RemoveEntities {
+    pub entity_ids: Vec<VarInt>,
+}
+
+// 0x3E
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct SetHeadRotation {
+    pub entity_id: VarInt,
+    pub head_yaw: Angle,
+}
+
+// 0x4F, sent for leashes. the holding entity is -1 when the leash is detached.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct LinkEntities {
+    pub attached_entity_id: i32,
+    pub holding_entity_id: i32,
+}
+
+// 0x50, the velocity is in 1/8000 of a block per tick.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct SetEntityVelocity {
+    pub entity_id: VarInt,
+    pub velocity_x: i16,
+    pub velocity_y: i16,
+    pub velocity_z: i16,
+}
+
+// 0x55, the passengers replace the ones the entity had before.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct SetPassengers {
+    pub entity_id: VarInt,
+    pub passengers: Vec<VarInt>,
+}
+
+// 0x64, for movements too far for a delta.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct TeleportEntity {
+    pub entity_id: VarInt,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub yaw: Angle,
+    pub pitch: Angle,
+    pub on_ground: bool,
+}
+
+// 0x06, stages go from 0 to 9, anything else removes the cracks.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct SetBlockDestroyStage {
+    pub entity_id: VarInt,
+    pub location: Position,
+    pub destroy_stage: i8,
+}
+
+// 0x07
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct BlockEntityData {
+    pub location: Position,
+    pub ty: VarInt,
+    #[versioned]
+    pub data: OptionalValue,
+}
+
+// 0x08, the meaning of the action id and parameter depends on the block, e.g. a note block or a chest opening.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct BlockAction {
+    pub location: Position,
+    pub action_id: u8,
+    pub action_parameter: u8,
+    pub block_type: VarInt,
+}
+
+// 0x09
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct BlockUpdate {
+    pub location: Position,
+    pub block_state: VarInt,
+}
+
+// 0x3F
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct UpdateSectionBlocks {
+    pub section: SectionPosition,
+    #[until(V1_20)]
+    pub suppress_light_updates: Option<bool>,
+    pub blocks: Vec<SectionBlock>,
+}
+
+// 0x18, a message that isn't signed but still uses a chat type, e.g. from /say in the console.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct DisguisedChat {
+    #[versioned]
+    pub message: TextComponent,
+    #[versioned]
+    pub chat_type: ChatTypeReference,
+    #[versioned]
+    pub sender_name: TextComponent,
+    #[versioned]
+    pub target_name: Option<TextComponent>,
+}
+
+// 0x31
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct PlayerChat {
+    pub sender: Uuid,
+    pub index: VarInt,
+    pub message_signature: Option<MessageSignature>,
+    pub message: String,
+    pub timestamp: i64,
+    pub salt: i64,
+    pub previous_messages: Vec<PreviousMessage>,
+    #[versioned]
+    pub unsigned_content: Option<TextComponent>,
+    pub filter: FilterType,
+    #[versioned]
+    pub chat_type: ChatTypeReference,
+    #[versioned]
+    pub sender_name: TextComponent,
+    #[versioned]
+    pub target_name: Option<TextComponent>,
+}
+
+// 0x60, the overlay shows the message above the hotbar instead of in the chat.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct SystemChat {
+    #[versioned]
+    pub content: TextComponent,
+    pub overlay: bool,
+}
+
+// 0x35
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct PlayerInfoRemove {
+    pub uuids: Vec<Uuid>,
+}
+
+// 0x36, the tab list. every entry has the fields of all the actions, in the order of the actions.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PlayerInfoUpdate {
+    pub actions: PlayerInfoActions,
+    pub entries: Vec<PlayerInfoEntry>,
+}
+
+impl VersionedDecodable for PlayerInfoUpdate {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        let actions = PlayerInfoActions::decode(reader)?;
+        let count = VarInt::decode(reader)?.0;
+        let mut entries = Vec::new();
+
+        for _ in 0..count {
+            entries.push(PlayerInfoEntry::decode_actions(reader, actions, version)?);
+        }
+
+        Ok(PlayerInfoUpdate { actions, entries })
+    }
+}
+
+impl VersionedEncodable for PlayerInfoUpdate {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        self.actions.encode(writer)?;
+        VarInt(self.entries.len() as i32).encode(writer)?;
+
+        for entry in &self.entries {
+            entry.encode_actions(writer, self.actions, version)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Decodable for PlayerInfoUpdate {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        Self::decode_versioned(reader, ProtocolVersion::LATEST)
+    }
+}
+
+impl Encodable for PlayerInfoUpdate {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        self.encode_versioned(writer, ProtocolVersion::LATEST)
+    }
+}
+
+// 0x34 in 1.19 and 0x37 in 1.19.1, replaced by Player Info Update and Remove in 1.19.3.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct LegacyPlayerInfo {
+    pub action: LegacyPlayerInfoAction,
+}
+
+// 0x2C, the window id is used by every other container packet until the window is closed.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct OpenScreen {
+    pub window_id: VarInt,
+    pub window_type: VarInt,
+    #[versioned]
+    pub title: TextComponent,
+}
+
+// 0x10, window 0 is the player's inventory. the state id has to be sent back with the next click.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct SetContainerContent {
+    pub window_id: u8,
+    pub state_id: VarInt,
+    #[versioned]
+    pub slots: Vec<Slot>,
+    #[versioned]
+    pub carried_item: Slot,
+}
+
+// 0x11, e.g. the progress of a furnace. what the property means depends on the window type.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct SetContainerProperty {
+    pub window_id: u8,
+    pub property: i16,
+    pub value: i16,
+}
+
+// 0x12, window -1 with slot -1 sets the carried item.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct SetContainerSlot {
+    pub window_id: i8,
+    pub state_id: VarInt,
+    pub slot: i16,
+    #[versioned]
+    pub item: Slot,
+}
+
+// 0x0F
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct ClientboundCloseContainer {
+    pub window_id: u8,
+}
+
+// 0x0E, the whole command tree as a flat list of nodes.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct Commands {
+    #[versioned]
+    pub nodes: Vec<CommandNode>,
+    pub root_index: VarInt,
+}
+
+// 0x69, every recipe before 1.21.2. since then the recipe book gets display entries from Recipe Book Add instead,
+// which isn't modelled until the 1.21.2 play ids are.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct UpdateRecipes {
+    #[versioned]
+    pub recipes: Vec<Recipe>,
+}
+
+// 0x6A, also sent again after a /reload.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct UpdateTags {
+    pub registries: Vec<RegistryTags>,
+}
+
+// 0x0A, the uuid identifies the bar in the following updates.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct BossBar {
+    pub uuid: Uuid,
+    #[versioned]
+    pub action: BossBarAction,
+}
+
+// 0x5B, the title is only shown once the animation times were sent, or with the default ones.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct SetTitleText {
+    #[versioned]
+    pub text: TextComponent,
+}
+
+// 0x59
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct SetSubtitleText {
+    #[versioned]
+    pub text: TextComponent,
+}
+
+// 0x5C, all of them in ticks.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct SetTitleAnimationTimes {
+    pub fade_in: i32,
+    pub stay: i32,
+    pub fade_out: i32,
+}
+
+// 0x0C, resetting also sets the animation times back to their defaults.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct ClearTitles {
+    pub reset: bool,
+}
+
+// 0x42
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct SetActionBarText {
+    #[versioned]
+    pub text: TextComponent,
+}
+
+// 0x61, an empty text component removes the header or footer.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct SetTabListHeaderAndFooter {
+    #[versioned]
+    pub header: TextComponent,
+    #[versioned]
+    pub footer: TextComponent,
+}
+
+// 0x54
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct UpdateObjectives {
+    pub objective_name: String,
+    #[versioned]
+    pub action: ObjectiveAction,
+}
+
+// 0x4D, an empty objective name clears the slot.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct DisplayObjective {
+    #[versioned]
+    pub position: DisplaySlot,
+    pub objective_name: String,
+}
+
+// 0x57, the entity is a player name or the uuid of another entity.
+#[derive(Debug, PartialEq, Clone)]
+pub struct UpdateScore {
+    pub entity_name: String,
+    pub objective_name: String,
+    // before 1.20.3 a missing value removes the score, since then that's done with Reset Score.
+    pub value: Option<VarInt>,
+    // since 1.20.3
+    pub display_name: Option<TextComponent>,
+    pub number_format: Option<NumberFormat>,
+}
+
+impl VersionedDecodable for UpdateScore {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        let entity_name = String::decode(reader)?;
+
+        if version >= ProtocolVersion::V1_20_3 {
+            return Ok(UpdateScore {
+                entity_name,
+                objective_name: String::decode(reader)?,
+                value: Some(VarInt::decode(reader)?),
+                display_name: Option::decode_versioned(reader, version)?,
+                number_format: Option::decode_versioned(reader, version)?,
+            });
+        }
+
+        let remove = VarInt::decode(reader)?.0 == 1;
+        let objective_name = String::decode(reader)?;
+
+        let value = if remove {
+            None
+        } else {
+            Some(VarInt::decode(reader)?)
+        };
+
+        Ok(UpdateScore {
+            entity_name,
+            objective_name,
+            value,
+            display_name: None,
+            number_format: None,
+        })
+    }
+}
+
+impl VersionedEncodable for UpdateScore {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        self.entity_name.encode(writer)?;
+
+        if version >= ProtocolVersion::V1_20_3 {
+            self.objective_name.encode(writer)?;
+            required(&self.value, "value")?.encode(writer)?;
+            self.display_name.encode_versioned(writer, version)?;
+            return self.number_format.encode_versioned(writer, version);
+        }
+
+        VarInt(self.value.is_none() as i32).encode(writer)?;
+        self.objective_name.encode(writer)?;
+
+        match &self.value {
+            Some(value) => value.encode(writer),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Decodable for UpdateScore {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        Self::decode_versioned(reader, ProtocolVersion::LATEST)
+    }
+}
+
+impl Encodable for UpdateScore {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        self.encode_versioned(writer, ProtocolVersion::LATEST)
+    }
+}
+
+// 0x42, since 1.20.3. without an objective the entity's scores are removed from every objective.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct ResetScore {
+    pub entity_name: String,
+    pub objective_name: Option<String>,
+}
+
+// 0x56
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct UpdateTeams {
+    pub team_name: String,
+    #[versioned]
+    pub action: TeamAction,
+}
+
+// 0x21. the data depends on the event, e.g. the record of a jukebox or the block of a broken block.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct WorldEvent {
+    pub event: i32,
+    pub location: Position,
+    pub data: i32,
+    // plays the sound at the same volume everywhere, e.g. for the wither spawning.
+    pub disable_relative_volume: bool,
+}
+
+// 0x22. the particle moved from the start of the packet to its end in 1.20.5.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Particle {
+    pub particle: ParticleData,
+    // shows the particle up to 512 blocks away instead of 32.
+    pub long_distance: bool,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    // multiplied by a random number from a gaussian distribution, or the color of some particles with a count of 0.
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub offset_z: f32,
+    pub max_speed: f32,
+    pub count: i32,
+}
+
+impl VersionedDecodable for Particle {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        let id = if version < ProtocolVersion::V1_20_5 {
+            Some(VarInt::decode(reader)?)
+        } else {
+            None
+        };
+
+        let long_distance = bool::decode(reader)?;
+        let x = f64::decode(reader)?;
+        let y = f64::decode(reader)?;
+        let z = f64::decode(reader)?;
+        let offset_x = f32::decode(reader)?;
+        let offset_y = f32::decode(reader)?;
+        let offset_z = f32::decode(reader)?;
+        let max_speed = f32::decode(reader)?;
+        let count = i32::decode(reader)?;
+
+        let particle = match id {
+            Some(id) => ParticleData::decode_data(reader, id, version)?,
+            None => ParticleData::decode_versioned(reader, version)?,
+        };
+
+        Ok(Particle {
+            particle,
+            long_distance,
+            x,
+            y,
+            z,
+            offset_x,
+            offset_y,
+            offset_z,
+            max_speed,
+            count,
+        })
+    }
+}
+
+impl VersionedEncodable for Particle {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        if version < ProtocolVersion::V1_20_5 {
+            self.particle.id(version)?.encode(writer)?;
+        }
+
+        self.long_distance.encode(writer)?;
+        self.x.encode(writer)?;
+        self.y.encode(writer)?;
+        self.z.encode(writer)?;
+        self.offset_x.encode(writer)?;
+        self.offset_y.encode(writer)?;
+        self.offset_z.encode(writer)?;
+        self.max_speed.encode(writer)?;
+        self.count.encode(writer)?;
+
+        if version < ProtocolVersion::V1_20_5 {
+            return self.particle.encode_data(writer, version);
+        }
+
+        self.particle.encode_versioned(writer, version)
+    }
+}
+
+impl Decodable for Particle {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        Self::decode_versioned(reader, ProtocolVersion::LATEST)
+    }
+}
+
+impl Encodable for Particle {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        self.encode_versioned(writer, ProtocolVersion::LATEST)
+    }
+}
+
+// 0x5D
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct EntitySoundEffect {
+    pub sound: SoundReference,
+    pub category: SoundCategory,
+    pub entity_id: VarInt,
+    pub volume: f32,
+    pub pitch: f32,
+    // the seed of the random sound variant.
+    pub seed: i64,
+}
+
+// 0x5E. the coordinates are fixed-point numbers multiplied by 8.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct SoundEffect {
+    pub sound: SoundReference,
+    pub category: SoundCategory,
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    pub volume: f32,
+    pub pitch: f32,
+    pub seed: i64,
+}
+
+// 0x5F. stops every sound that matches both, without either all sounds are stopped.
+#[derive(Debug, PartialEq, Clone)]
+pub struct StopSound {
+    pub category: Option<SoundCategory>,
+    pub sound: Option<Identifier>,
+}
+
+impl VersionedDecodable for StopSound {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        _version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        let flags = u8::decode(reader)?;
+
+        let category = match flags & 0x01 {
+            0 => None,
+            _ => Some(SoundCategory::decode(reader)?),
+        };
+
+        let sound = match flags & 0x02 {
+            0 => None,
+            _ => Some(Identifier::decode(reader)?),
+        };
+
+        Ok(StopSound { category, sound })
+    }
+}
+
+impl VersionedEncodable for StopSound {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        _version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        let flags = self.category.is_some() as u8 | (self.sound.is_some() as u8) << 1;
+        flags.encode(writer)?;
+
+        if let Some(category) = &self.category {
+            category.encode(writer)?;
+        }
+
+        if let Some(sound) = &self.sound {
+            sound.encode(writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Decodable for StopSound {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        Self::decode_versioned(reader, ProtocolVersion::LATEST)
+    }
+}
+
+impl Encodable for StopSound {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        self.encode_versioned(writer, ProtocolVersion::LATEST)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        nbt,
+        packets::registry::Packet as _,
+        recipe::RecipeData,
+        slot::{ItemData, ItemStack},
+        tags::Tag,
+    };
+
+    use super::*;
+
+    fn round_trip<T>(packet: &T, version: ProtocolVersion) -> T
+    where
+        T: VersionedEncodable + VersionedDecodable,
+    {
+        let mut buf = Vec::new();
+        packet.encode_versioned(&mut buf, version).unwrap();
+
+        let mut reader = &buf[..];
+        let decoded = T::decode_versioned(&mut reader, version).unwrap();
+        assert!(reader.is_empty());
+
+        decoded
+    }
+
+    fn spawn_info(version: ProtocolVersion) -> SpawnInfo {
+        SpawnInfo {
+            dimension_type: if version >= ProtocolVersion::V1_20_5 {
+                DimensionType::Id(VarInt(3))
+            } else {
+                DimensionType::Name("minecraft:overworld".into())
+            },
+            dimension_name: "minecraft:overworld".into(),
+            hashed_seed: 5,
+            game_mode: 1,
+            previous_game_mode: -1,
+            is_debug: false,
+            is_flat: true,
+            death_location: Some(DeathLocation {
+                dimension_name: "minecraft:the_end".into(),
+                location: Position {
+                    x: -5,
+                    y: 70,
+                    z: 1000,
+                },
+            }),
+            portal_cooldown: (version >= ProtocolVersion::V1_20).then_some(VarInt(0)),
+        }
+    }
+
+    fn join_game(version: ProtocolVersion) -> JoinGame {
+        JoinGame {
+            entity_id: 9,
+            is_hardcore: false,
+            dimension_names: vec!["minecraft:overworld".into()],
+            registry_codec: (version < ProtocolVersion::V1_20_2)
+                .then(|| nbt!({ "minecraft:dimension_type": {} })),
+            max_players: VarInt(20),
+            view_distance: VarInt(10),
+            simulation_distance: VarInt(8),
+            reduced_debug_info: false,
+            enable_respawn_screen: true,
+            do_limited_crafting: (version >= ProtocolVersion::V1_20_2).then_some(false),
+            spawn_info: spawn_info(version),
+            enforces_secure_chat: (version >= ProtocolVersion::V1_20_5).then_some(true),
+        }
+    }
+
+    #[test]
+    fn join_game_layouts() {
+        for (version, id) in [
+            (ProtocolVersion::V1_19_3, 0x24),
+            (ProtocolVersion::V1_20, 0x28),
+            (ProtocolVersion::V1_20_2, 0x29),
+            (ProtocolVersion::V1_20_5, 0x2B),
+            (ProtocolVersion::V1_21, 0x2B),
+        ] {
+            let packet = join_game(version);
+            assert_eq!(round_trip(&packet, version), packet);
+            assert_eq!(JoinGame::id_for(version), Some(id));
+        }
+
+        // the fields of newer versions are required there.
+        let packet = join_game(ProtocolVersion::V1_20_3);
+        assert!(packet.encode(&mut Vec::new()).is_err());
+        assert!(join_game(ProtocolVersion::V1_21)
+            .encode_versioned(&mut Vec::new(), ProtocolVersion::V1_20)
+            .is_err());
+    }
+
+    #[test]
+    fn respawn_layouts() {
+        for version in [
+            ProtocolVersion::V1_20,
+            ProtocolVersion::V1_20_2,
+            ProtocolVersion::V1_21,
+        ] {
+            let packet = Respawn {
+                spawn_info: spawn_info(version),
+                data_kept: KEEP_METADATA,
+            };
+            assert_eq!(round_trip(&packet, version), packet);
+        }
+
+        // before 1.20 a single flag kept everything.
+        let packet = Respawn {
+            spawn_info: spawn_info(ProtocolVersion::V1_19_3),
+            data_kept: KEEP_METADATA,
+        };
+        assert_eq!(
+            round_trip(&packet, ProtocolVersion::V1_19_3).data_kept,
+            KEEP_ATTRIBUTES | KEEP_METADATA
+        );
+    }
+
+    #[test]
+    fn update_score_layouts() {
+        let removed = UpdateScore {
+            entity_name: "Steve".into(),
+            objective_name: "kills".into(),
+            value: None,
+            display_name: None,
+            number_format: None,
+        };
+
+        // before 1.20.3 the action comes between the names and a removal has no value.
+        let mut buf = Vec::new();
+        removed
+            .encode_versioned(&mut buf, ProtocolVersion::V1_20_2)
+            .unwrap();
+        assert_eq!(buf[6], 1);
+        assert_eq!(buf.len(), 13);
+        assert_eq!(round_trip(&removed, ProtocolVersion::V1_20_2), removed);
+        assert!(removed.encode(&mut Vec::new()).is_err());
+
+        let updated = UpdateScore {
+            value: Some(VarInt(3)),
+            ..removed
+        };
+        let mut buf = Vec::new();
+        updated
+            .encode_versioned(&mut buf, ProtocolVersion::V1_20_2)
+            .unwrap();
+        assert_eq!(buf[6], 0);
+
+        let styled = UpdateScore {
+            display_name: Some("S".into()),
+            number_format: Some(NumberFormat::Fixed(Box::new("!".into()))),
+            ..updated
+        };
+        for version in [ProtocolVersion::V1_20_3, ProtocolVersion::V1_21] {
+            assert_eq!(round_trip(&styled, version), styled);
+        }
+    }
+
+    fn particle(particle: ParticleData) -> Particle {
+        Particle {
+            particle,
+            long_distance: true,
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+            offset_x: 0.1,
+            offset_y: 0.2,
+            offset_z: 0.3,
+            max_speed: 1.0,
+            count: 5,
+        }
+    }
+
+    #[test]
+    fn particle_layouts() {
+        use crate::particle::VibrationSource;
+
+        let particles = [
+            ParticleData::Simple("minecraft:flame".into()),
+            ParticleData::Block(VarInt(10)),
+            ParticleData::Dust {
+                red: 1.0,
+                green: 0.5,
+                blue: 0.0,
+                scale: 2.0,
+            },
+            ParticleData::DustColorTransition {
+                from_red: 1.0,
+                from_green: 0.0,
+                from_blue: 0.0,
+                to_red: 0.0,
+                to_green: 1.0,
+                to_blue: 0.0,
+                scale: 1.5,
+            },
+            ParticleData::Item(Slot(None)),
+            ParticleData::Vibration {
+                destination: VibrationSource::Block(Position { x: 1, y: 2, z: 3 }),
+                ticks: VarInt(20),
+            },
+            ParticleData::Shriek { delay: VarInt(3) },
+        ];
+
+        for version in [
+            ProtocolVersion::V1_19_3,
+            ProtocolVersion::V1_20_3,
+            ProtocolVersion::V1_20_5,
+            ProtocolVersion::V1_21,
+        ] {
+            for data in particles.iter().cloned() {
+                let packet = particle(data);
+                assert_eq!(round_trip(&packet, version), packet);
+            }
+        }
+
+        // the particle came first until 1.20.5, then it moved to the end.
+        let shriek = particle(ParticleData::Shriek { delay: VarInt(3) });
+        let mut buf = Vec::new();
+        shriek
+            .encode_versioned(&mut buf, ProtocolVersion::V1_19_3)
+            .unwrap();
+        assert_eq!(buf[..2], [92, 1]);
+        assert_eq!(buf[buf.len() - 1], 3);
+
+        let mut buf = Vec::new();
+        shriek
+            .encode_versioned(&mut buf, ProtocolVersion::V1_21)
+            .unwrap();
+        assert_eq!(buf[0], 1);
+        assert_eq!(buf[buf.len() - 2..], [99, 3]);
+
+        assert!(particle(ParticleData::EntityEffect { color: -1 })
+            .encode_versioned(&mut Vec::new(), ProtocolVersion::V1_20_3)
+            .is_err());
+    }
+
+    #[test]
+    fn sound_references() {
+        use crate::sound::SoundEvent;
+
+        let sound = |sound| SoundEffect {
+            sound,
+            category: SoundCategory::Blocks,
+            x: 8,
+            y: 16,
+            z: -8,
+            volume: 1.0,
+            pitch: 1.0,
+            seed: 7,
+        };
+
+        let mut buf = Vec::new();
+        sound(SoundReference::Id(VarInt(4)))
+            .encode(&mut buf)
+            .unwrap();
+        assert_eq!(buf[0], 5);
+
+        for reference in [
+            SoundReference::Id(VarInt(4)),
+            SoundReference::Inline(SoundEvent {
+                name: "custom:boom".into(),
+                fixed_range: Some(16.0),
+            }),
+        ] {
+            let packet = sound(reference);
+            assert_eq!(round_trip(&packet, ProtocolVersion::V1_21), packet);
+        }
+    }
+
+    #[test]
+    fn update_section_blocks_suppresses_light_updates_until_1_20() {
+        let packet = |suppress_light_updates| UpdateSectionBlocks {
+            section: SectionPosition { x: 1, y: -4, z: 2 },
+            suppress_light_updates,
+            blocks: vec![SectionBlock {
+                block_state: 24000,
+                x: 15,
+                y: 3,
+                z: 9,
+            }],
+        };
+
+        let mut buf = Vec::new();
+        packet(Some(true))
+            .encode_versioned(&mut buf, ProtocolVersion::V1_19_4)
+            .unwrap();
+        assert_eq!(buf[8..], [1, 1, 0x93, 0x9F, 0xF0, 0x2E]);
+        assert_eq!(
+            round_trip(&packet(Some(true)), ProtocolVersion::V1_19_4),
+            packet(Some(true))
+        );
+        assert!(packet(None)
+            .encode_versioned(&mut Vec::new(), ProtocolVersion::V1_19_4)
+            .is_err());
+
+        let mut buf = Vec::new();
+        packet(None)
+            .encode_versioned(&mut buf, ProtocolVersion::V1_20)
+            .unwrap();
+        assert_eq!(buf[8..], [1, 0x93, 0x9F, 0xF0, 0x2E]);
+        assert_eq!(
+            round_trip(&packet(None), ProtocolVersion::V1_20),
+            packet(None)
+        );
+    }
+
+    #[test]
+    fn player_chat_layouts() {
+        use crate::{
+            chat::{ChatType, ChatTypeDecoration},
+            datatypes::bitset::BitSet,
+            identifier::RegistryReference,
+        };
+
+        let chat = |chat_type| PlayerChat {
+            sender: Uuid::from_bytes([7; 16]),
+            index: VarInt(2),
+            message_signature: Some(vec![9; 256].into()),
+            message: "hi".into(),
+            timestamp: 1,
+            salt: 2,
+            previous_messages: vec![
+                PreviousMessage::Id(0),
+                PreviousMessage::Signature(vec![1; 256].into()),
+            ],
+            unsigned_content: Some(TextComponent::text("hi!")),
+            filter: FilterType::PartiallyFiltered(BitSet(vec![3])),
+            chat_type: ChatTypeReference(chat_type),
+            sender_name: "Steve".into(),
+            target_name: Some("Alex".into()),
+        };
+
+        for version in [
+            ProtocolVersion::V1_19_3,
+            ProtocolVersion::V1_20_2,
+            ProtocolVersion::V1_20_3,
+        ] {
+            let packet = chat(RegistryReference::Id(VarInt(0)));
+            assert_eq!(round_trip(&packet, version), packet);
+        }
+
+        let inline = ChatType {
+            chat: ChatTypeDecoration {
+                translation_key: "chat.type.text".into(),
+                parameters: vec![VarInt(0), VarInt(2)],
+                style: nbt!({}),
+            },
+            narration: ChatTypeDecoration {
+                translation_key: "chat.type.text.narrate".into(),
+                parameters: Vec::new(),
+                style: nbt!({ "color": "red" }),
+            },
+        };
+        for chat_type in [
+            RegistryReference::Id(VarInt(0)),
+            RegistryReference::Inline(inline),
+        ] {
+            let packet = chat(chat_type);
+            assert_eq!(round_trip(&packet, ProtocolVersion::V1_21), packet);
+        }
+    }
+
+    fn stack(version: ProtocolVersion) -> Slot {
+        let data = if version < ProtocolVersion::V1_20_5 {
+            ItemData::Nbt(nbt::OptionalValue(None))
+        } else {
+            ItemData::Components {
+                added: vec![],
+                removed: vec![],
+            }
+        };
+
+        Slot(Some(ItemStack {
+            item_id: VarInt(12),
+            count: 4,
+            data,
+        }))
+    }
+
+    fn recipes(version: ProtocolVersion) -> Vec<Recipe> {
+        let ingredient = vec![stack(version), Slot(None)];
+
+        vec![
+            Recipe {
+                id: "minecraft:torch".into(),
+                ty: "minecraft:crafting_shaped".into(),
+                data: RecipeData::Shaped {
+                    group: "".into(),
+                    category: VarInt(2),
+                    width: VarInt(1),
+                    height: VarInt(2),
+                    ingredients: vec![ingredient.clone(), vec![]],
+                    result: stack(version),
+                    show_notification: true,
+                },
+            },
+            Recipe {
+                id: "minecraft:bundle".into(),
+                ty: "minecraft:crafting_shapeless".into(),
+                data: RecipeData::Shapeless {
+                    group: "bundle".into(),
+                    category: VarInt(3),
+                    ingredients: vec![ingredient.clone(), ingredient.clone()],
+                    result: stack(version),
+                },
+            },
+            Recipe {
+                id: "minecraft:armor_dye".into(),
+                ty: "minecraft:crafting_special_armordye".into(),
+                data: RecipeData::Special {
+                    category: VarInt(3),
+                },
+            },
+            Recipe {
+                id: "minecraft:iron_ingot_from_blasting".into(),
+                ty: "minecraft:blasting".into(),
+                data: RecipeData::Cooking {
+                    group: "iron_ingot".into(),
+                    category: VarInt(1),
+                    ingredient: ingredient.clone(),
+                    result: stack(version),
+                    experience: 0.7,
+                    cooking_time: VarInt(100),
+                },
+            },
+            Recipe {
+                id: "minecraft:stone_slab_from_stonecutting".into(),
+                ty: "minecraft:stonecutting".into(),
+                data: RecipeData::Stonecutting {
+                    group: "".into(),
+                    ingredient: ingredient.clone(),
+                    result: stack(version),
+                },
+            },
+            Recipe {
+                id: "minecraft:netherite_sword_smithing".into(),
+                ty: "minecraft:smithing_transform".into(),
+                data: RecipeData::SmithingTransform {
+                    template: ingredient.clone(),
+                    base: ingredient.clone(),
+                    addition: vec![],
+                    result: stack(version),
+                },
+            },
+            Recipe {
+                id: "minecraft:coast_armor_trim_smithing_template_smithing_trim".into(),
+                ty: "minecraft:smithing_trim".into(),
+                data: RecipeData::SmithingTrim {
+                    template: ingredient.clone(),
+                    base: vec![],
+                    addition: ingredient,
+                },
+            },
+        ]
+    }
+
+    #[test]
+    fn update_recipes_layouts() {
+        for (version, id) in [
+            (ProtocolVersion::V1_20_2, 0x6F),
+            (ProtocolVersion::V1_20_3, 0x73),
+            (ProtocolVersion::V1_21, 0x77),
+        ] {
+            let packet = UpdateRecipes {
+                recipes: recipes(version),
+            };
+            assert_eq!(round_trip(&packet, version), packet);
+            assert_eq!(UpdateRecipes::id_for(version), Some(id));
+        }
+        assert_eq!(UpdateRecipes::id_for(ProtocolVersion::V1_21_2), None);
+
+        // the smithing table had no template before 1.20.
+        let packet = UpdateRecipes {
+            recipes: vec![Recipe {
+                id: "minecraft:netherite_sword_smithing".into(),
+                ty: "minecraft:smithing".into(),
+                data: RecipeData::LegacySmithing {
+                    base: vec![stack(ProtocolVersion::V1_19_3)],
+                    addition: vec![],
+                    result: stack(ProtocolVersion::V1_19_3),
+                },
+            }],
+        };
+        assert_eq!(round_trip(&packet, ProtocolVersion::V1_19_3), packet);
+    }
+
+    #[test]
+    fn recipe_types_moved_into_the_serializer_registry_in_1_20_5() {
+        let mut buf = Vec::new();
+        recipes(ProtocolVersion::V1_20_3)[0]
+            .encode_versioned(&mut buf, ProtocolVersion::V1_20_3)
+            .unwrap();
+        assert_eq!(&buf[1..26], b"minecraft:crafting_shaped");
+
+        let recipe = &recipes(ProtocolVersion::V1_21)[0];
+
+        let mut buf = Vec::new();
+        recipe
+            .encode_versioned(&mut buf, ProtocolVersion::V1_21)
+            .unwrap();
+        assert_eq!(&buf[1..16], b"minecraft:torch");
+        assert_eq!(buf[16], 0);
+
+        let unknown = Recipe {
+            ty: "minecraft:crafting_transmute".into(),
+            ..recipe.clone()
+        };
+        assert!(unknown
+            .encode_versioned(&mut Vec::new(), ProtocolVersion::V1_21)
+            .is_err());
+
+        // 23 is past the last serializer.
+        buf[16] = 23;
+        assert!(Recipe::decode_versioned(&mut &buf[..17], ProtocolVersion::V1_21).is_err());
+
+        // the width and height came first before 1.20.3.
+        let mut buf = Vec::new();
+        recipes(ProtocolVersion::V1_20_2)[0]
+            .encode_versioned(&mut buf, ProtocolVersion::V1_20_2)
+            .unwrap();
+        assert_eq!(&buf[42..45], &[1, 2, 0]);
+    }
+
+    #[test]
+    fn update_tags() {
+        let packet = UpdateTags {
+            registries: vec![RegistryTags {
+                registry: "minecraft:block".into(),
+                tags: vec![
+                    Tag {
+                        name: "minecraft:logs".into(),
+                        entries: vec![VarInt(1), VarInt(2)],
+                    },
+                    Tag {
+                        name: "minecraft:dirt".into(),
+                        entries: vec![],
+                    },
+                ],
+            }],
+        };
+
+        for (version, id) in [
+            (ProtocolVersion::V1_19_3, 0x6A),
+            (ProtocolVersion::V1_20_3, 0x74),
+            (ProtocolVersion::V1_21, 0x78),
+        ] {
+            assert_eq!(round_trip(&packet, version), packet);
+            assert_eq!(UpdateTags::id_for(version), Some(id));
+        }
+    }
+}
@@ -1,16 +1,15 @@
-use crate::VarInt;
 use proc_macros::MinecraftPacket;
 
+use crate::status::ServerStatus;
+
 // 0x00
 #[derive(MinecraftPacket, Debug, PartialEq)]
 pub struct StatusResponse {
-    pub id: VarInt,
-    pub response: String,
+    pub response: ServerStatus,
 }
 
 // 0x01
 #[derive(MinecraftPacket, Debug, PartialEq)]
 pub struct PingResponse {
-    pub id: VarInt,
     pub payload: i64,
 }
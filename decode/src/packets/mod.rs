@@ -1,18 +1,17 @@
 pub mod clientbound;
+pub mod registry;
 pub mod serverbound;
 
 use std::io::{Cursor, Read, Write};
 
 use proc_macros::ParsePacket;
-use types::packet::{ClientState, PacketDirection};
+use types::packet::{ClientState, PacketDirection, ProtocolVersion};
 
 // not actually unused imports, we use them in the macro
 #[allow(unused_imports)]
-use self::clientbound::{login::*, play::*, status::*};
+use self::clientbound::{configuration::*, login::*, play::*, status::*};
 #[allow(unused_imports)]
-use self::clientbound::{play::*, status::*};
-#[allow(unused_imports)]
-use self::serverbound::{login::*, play::*, status::*};
+use self::serverbound::{configuration::*, handshake::*, login::*, play::*, status::*};
 
 pub trait Decodable: Sized {
     fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error>;
@@ -22,38 +21,378 @@ pub trait Encodable {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error>;
 }
 
+// for types whose layout changed between protocol versions.
+pub trait VersionedDecodable: Sized {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error>;
+}
+
+pub trait VersionedEncodable {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error>;
+}
+
+// the same error the MinecraftPacket derive returns, for the packets and fields that are implemented by hand.
+pub(crate) fn required<'a, T>(value: &'a Option<T>, name: &str) -> Result<&'a T, std::io::Error> {
+    value.as_ref().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("{} is required by this protocol version.", name),
+        )
+    })
+}
+
+// the play state has the ids of 1.19.3, 1.19.4 (unchanged in 1.20), 1.20.2, 1.20.3 and 1.20.5 (unchanged in 1.21),
+// an id is used from its version until the next entry. the play ids of 1.21.2 and of the versions before 1.19.3 aren't
+// listed, so those packets are rejected instead of decoded as the wrong packet. the packets that were replaced in
+// 1.19.3 list their older ids as well. the configuration state only exists since 1.20.2.
+// packets without a version in their ids are the same in every version.
 #[derive(ParsePacket)]
 pub enum PacketType {
+    #[packet(0x00, Serverbound, Handshake, Handshake)]
+    HandshakeType,
+    #[packet(0x00, Serverbound, Status, StatusRequest)]
+    StatusRequestType,
+    #[packet(0x01, Serverbound, Status, PingRequest)]
+    PingRequestType,
+    #[packet(0x00, Serverbound, Login, LoginStart)]
+    LoginStartType,
+    #[packet(0x01, Serverbound, Login, EncryptionResponse)]
+    EncryptionResponseType,
+    #[packet(0x02, Serverbound, Login, LoginPluginResponse)]
+    LoginPluginResponseType,
+    #[packet([V1_20_2 = 0x03], Serverbound, Login, LoginAcknowledged)]
+    LoginAcknowledgedType,
+    #[packet([V1_20_5 = 0x04], Serverbound, Login, CookieResponse)]
+    CookieResponseType,
+    #[packet(0x00, Clientbound, Login, LoginDisconnect)]
+    LoginDisconnectType,
+    #[packet(0x01, Clientbound, Login, EncryptionRequest)]
+    EncryptionRequestType,
+    #[packet(0x02, Clientbound, Login, LoginSuccess)]
+    LoginSuccessType,
+    #[packet(0x03, Clientbound, Login, SetCompression)]
+    SetCompressionType,
+    #[packet(0x04, Clientbound, Login, LoginPluginRequest)]
+    LoginPluginRequestType,
+    #[packet([V1_20_5 = 0x05], Clientbound, Login, CookieRequest)]
+    CookieRequestType,
+    #[packet([V1_20_2 = 0x02, V1_20_5 = 0x03], Serverbound, Configuration, AcknowledgeFinishConfiguration)]
+    AcknowledgeFinishConfigurationType,
+    #[packet([V1_20_2 = 0x05, V1_20_5 = 0x06], Serverbound, Configuration, ResourcePackResponse)]
+    ResourcePackResponseType,
+    #[packet([V1_20_5 = 0x07], Serverbound, Configuration, ServerboundKnownPacks)]
+    ServerboundKnownPacksType,
+    #[packet([V1_20_2 = 0x02, V1_20_5 = 0x03], Clientbound, Configuration, FinishConfiguration)]
+    FinishConfigurationType,
+    #[packet([V1_20_2 = 0x05, V1_20_5 = 0x07], Clientbound, Configuration, RegistryData)]
+    RegistryDataType,
+    #[packet([V1_20_3 = 0x06, V1_20_5 = 0x08], Clientbound, Configuration, RemoveResourcePack)]
+    RemoveResourcePackType,
+    #[packet([V1_20_2 = 0x06, V1_20_3 = 0x07, V1_20_5 = 0x09], Clientbound, Configuration, AddResourcePack)]
+    AddResourcePackType,
+    #[packet([V1_20_2 = 0x07, V1_20_3 = 0x08, V1_20_5 = 0x0C], Clientbound, Configuration, FeatureFlags)]
+    FeatureFlagsType,
+    #[packet([V1_20_2 = 0x08, V1_20_3 = 0x09, V1_20_5 = 0x0D], Clientbound, Configuration, ConfigurationUpdateTags)]
+    ConfigurationUpdateTagsType,
+    #[packet([V1_20_5 = 0x0E], Clientbound, Configuration, ClientboundKnownPacks)]
+    ClientboundKnownPacksType,
     #[packet(0x00, Clientbound, Status, StatusResponse)]
     StatusResponseType,
     #[packet(0x01, Clientbound, Status, PingResponse)]
     PingResponseType,
-    #[packet(0x00, Clientbound, Play, SpawnEntity)]
+    #[packet([V1_19 = 0x34, V1_19_1..V1_19_3 = 0x37], Clientbound, Play, LegacyPlayerInfo)]
+    LegacyPlayerInfoType,
+    #[packet([V1_19_3 = 0x00, V1_19_4..V1_21_2 = 0x01], Clientbound, Play, SpawnEntity)]
     SpawnEntityType,
-    #[packet(0x01, Clientbound, Play, SpawnExperienceOrb)]
+    #[packet([V1_19_3 = 0x01, V1_19_4..V1_21_2 = 0x02], Clientbound, Play, SpawnExperienceOrb)]
     SpawnExperienceOrbType,
-    #[packet(0x02, Clientbound, Play, SpawnPlayer)]
+    #[packet([V1_19_3 = 0x02, V1_19_4..V1_20_2 = 0x03], Clientbound, Play, SpawnPlayer)]
     SpawnPlayerType,
-    #[packet(0x03, Clientbound, Play, EntityAnimation)]
+    #[packet([V1_19_3 = 0x03, V1_19_4 = 0x04, V1_20_2..V1_21_2 = 0x03], Clientbound, Play, EntityAnimation)]
     EntityAnimationType,
-    #[packet(0x04, Clientbound, Play, AwardStatistics)]
+    #[packet([V1_19_3 = 0x04, V1_19_4 = 0x05, V1_20_2..V1_21_2 = 0x04], Clientbound, Play, AwardStatistics)]
     AwardStatisticsType,
+    #[packet([V1_19_3 = 0x06, V1_19_4 = 0x07, V1_20_2..V1_21_2 = 0x06], Clientbound, Play, SetBlockDestroyStage)]
+    SetBlockDestroyStageType,
+    #[packet([V1_19_3 = 0x07, V1_19_4 = 0x08, V1_20_2..V1_21_2 = 0x07], Clientbound, Play, BlockEntityData)]
+    BlockEntityDataType,
+    #[packet([V1_19_3 = 0x08, V1_19_4 = 0x09, V1_20_2..V1_21_2 = 0x08], Clientbound, Play, BlockAction)]
+    BlockActionType,
+    #[packet([V1_19_3 = 0x09, V1_19_4 = 0x0A, V1_20_2..V1_21_2 = 0x09], Clientbound, Play, BlockUpdate)]
+    BlockUpdateType,
+    #[packet([V1_19_3 = 0x0A, V1_19_4 = 0x0B, V1_20_2..V1_21_2 = 0x0A], Clientbound, Play, BossBar)]
+    BossBarType,
+    #[packet([V1_19_3 = 0x0C, V1_19_4 = 0x0E, V1_20_2..V1_21_2 = 0x0F], Clientbound, Play, ClearTitles)]
+    ClearTitlesType,
+    #[packet([V1_19_3 = 0x0E, V1_19_4 = 0x10, V1_20_2..V1_21_2 = 0x11], Clientbound, Play, Commands)]
+    CommandsType,
+    #[packet([V1_19_3 = 0x0F, V1_19_4 = 0x11, V1_20_2..V1_21_2 = 0x12], Clientbound, Play, ClientboundCloseContainer)]
+    ClientboundCloseContainerType,
+    #[packet([V1_19_3 = 0x10, V1_19_4 = 0x12, V1_20_2..V1_21_2 = 0x13], Clientbound, Play, SetContainerContent)]
+    SetContainerContentType,
+    #[packet([V1_19_3 = 0x11, V1_19_4 = 0x13, V1_20_2..V1_21_2 = 0x14], Clientbound, Play, SetContainerProperty)]
+    SetContainerPropertyType,
+    #[packet([V1_19_3 = 0x12, V1_19_4 = 0x14, V1_20_2..V1_21_2 = 0x15], Clientbound, Play, SetContainerSlot)]
+    SetContainerSlotType,
+    #[packet([V1_19_3 = 0x17, V1_19_4 = 0x1A, V1_20_2 = 0x1B, V1_20_5..V1_21_2 = 0x1D], Clientbound, Play, Disconnect)]
+    DisconnectType,
+    #[packet([V1_19_3 = 0x18, V1_19_4 = 0x1B, V1_20_2 = 0x1C, V1_20_5..V1_21_2 = 0x1E], Clientbound, Play, DisguisedChat)]
+    DisguisedChatType,
+    #[packet([V1_19_3 = 0x1F, V1_19_4 = 0x23, V1_20_2 = 0x24, V1_20_5..V1_21_2 = 0x26], Clientbound, Play, ClientboundKeepAlive)]
+    ClientboundKeepAliveType,
+    #[packet([V1_19_3 = 0x20, V1_19_4 = 0x24, V1_20_2 = 0x25, V1_20_5..V1_21_2 = 0x27], Clientbound, Play, ChunkDataAndUpdateLight)]
+    ChunkDataAndUpdateLightType,
+    #[packet([V1_19_3 = 0x21, V1_19_4 = 0x25, V1_20_2 = 0x26, V1_20_5..V1_21_2 = 0x28], Clientbound, Play, WorldEvent)]
+    WorldEventType,
+    #[packet([V1_19_3 = 0x22, V1_19_4 = 0x26, V1_20_2 = 0x27, V1_20_5..V1_21_2 = 0x29], Clientbound, Play, Particle)]
+    ParticleType,
+    #[packet([V1_19_3 = 0x23, V1_19_4 = 0x27, V1_20_2 = 0x28, V1_20_5..V1_21_2 = 0x2A], Clientbound, Play, UpdateLight)]
+    UpdateLightType,
+    #[packet([V1_19_3 = 0x24, V1_19_4 = 0x28, V1_20_2 = 0x29, V1_20_5..V1_21_2 = 0x2B], Clientbound, Play, JoinGame)]
+    JoinGameType,
+    #[packet([V1_19_3 = 0x27, V1_19_4 = 0x2B, V1_20_2 = 0x2C, V1_20_5..V1_21_2 = 0x2E], Clientbound, Play, UpdateEntityPosition)]
+    UpdateEntityPositionType,
+    #[packet([V1_19_3 = 0x28, V1_19_4 = 0x2C, V1_20_2 = 0x2D, V1_20_5..V1_21_2 = 0x2F], Clientbound, Play, UpdateEntityPositionAndRotation)]
+    UpdateEntityPositionAndRotationType,
+    #[packet([V1_19_3 = 0x29, V1_19_4 = 0x2D, V1_20_2 = 0x2E, V1_20_5..V1_21_2 = 0x30], Clientbound, Play, UpdateEntityRotation)]
+    UpdateEntityRotationType,
+    #[packet([V1_19_3 = 0x2C, V1_19_4 = 0x30, V1_20_2 = 0x31, V1_20_5..V1_21_2 = 0x33], Clientbound, Play, OpenScreen)]
+    OpenScreenType,
+    #[packet([V1_19_3 = 0x2E, V1_19_4 = 0x32, V1_20_2 = 0x33, V1_20_5..V1_21_2 = 0x35], Clientbound, Play, Ping)]
+    PingType,
+    #[packet([V1_19_3 = 0x31, V1_19_4 = 0x35, V1_20_2 = 0x37, V1_20_5..V1_21_2 = 0x39], Clientbound, Play, PlayerChat)]
+    PlayerChatType,
+    #[packet([V1_19_3 = 0x35, V1_19_4 = 0x39, V1_20_2 = 0x3B, V1_20_5..V1_21_2 = 0x3D], Clientbound, Play, PlayerInfoRemove)]
+    PlayerInfoRemoveType,
+    #[packet([V1_19_3 = 0x36, V1_19_4 = 0x3A, V1_20_2 = 0x3C, V1_20_5..V1_21_2 = 0x3E], Clientbound, Play, PlayerInfoUpdate)]
+    PlayerInfoUpdateType,
+    #[packet([V1_19_3 = 0x3A, V1_19_4 = 0x3E, V1_20_2 = 0x40, V1_20_5..V1_21_2 = 0x42], Clientbound, Play, RemoveEntities)]
+    RemoveEntitiesType,
+    #[packet([V1_20_3 = 0x42, V1_20_5..V1_21_2 = 0x44], Clientbound, Play, ResetScore)]
+    ResetScoreType,
+    #[packet([V1_19_3 = 0x3D, V1_19_4 = 0x41, V1_20_2 = 0x43, V1_20_3 = 0x45, V1_20_5..V1_21_2 = 0x47], Clientbound, Play, Respawn)]
+    RespawnType,
+    #[packet([V1_19_3 = 0x3E, V1_19_4 = 0x42, V1_20_2 = 0x44, V1_20_3 = 0x46, V1_20_5..V1_21_2 = 0x48], Clientbound, Play, SetHeadRotation)]
+    SetHeadRotationType,
+    #[packet([V1_19_3 = 0x3F, V1_19_4 = 0x43, V1_20_2 = 0x45, V1_20_3 = 0x47, V1_20_5..V1_21_2 = 0x49], Clientbound, Play, UpdateSectionBlocks)]
+    UpdateSectionBlocksType,
+    #[packet([V1_19_3 = 0x42, V1_19_4 = 0x46, V1_20_2 = 0x48, V1_20_3 = 0x4A, V1_20_5..V1_21_2 = 0x4C], Clientbound, Play, SetActionBarText)]
+    SetActionBarTextType,
+    #[packet([V1_19_3 = 0x4D, V1_19_4 = 0x51, V1_20_2 = 0x53, V1_20_3 = 0x55, V1_20_5..V1_21_2 = 0x57], Clientbound, Play, DisplayObjective)]
+    DisplayObjectiveType,
+    #[packet([V1_19_3 = 0x4F, V1_19_4 = 0x53, V1_20_2 = 0x55, V1_20_3 = 0x57, V1_20_5..V1_21_2 = 0x59], Clientbound, Play, LinkEntities)]
+    LinkEntitiesType,
+    #[packet([V1_19_3 = 0x50, V1_19_4 = 0x54, V1_20_2 = 0x56, V1_20_3 = 0x58, V1_20_5..V1_21_2 = 0x5A], Clientbound, Play, SetEntityVelocity)]
+    SetEntityVelocityType,
+    #[packet([V1_19_3 = 0x54, V1_19_4 = 0x58, V1_20_2 = 0x5A, V1_20_3 = 0x5C, V1_20_5..V1_21_2 = 0x5E], Clientbound, Play, UpdateObjectives)]
+    UpdateObjectivesType,
+    #[packet([V1_19_3 = 0x55, V1_19_4 = 0x59, V1_20_2 = 0x5B, V1_20_3 = 0x5D, V1_20_5..V1_21_2 = 0x5F], Clientbound, Play, SetPassengers)]
+    SetPassengersType,
+    #[packet([V1_19_3 = 0x56, V1_19_4 = 0x5A, V1_20_2 = 0x5C, V1_20_3 = 0x5E, V1_20_5..V1_21_2 = 0x60], Clientbound, Play, UpdateTeams)]
+    UpdateTeamsType,
+    #[packet([V1_19_3 = 0x57, V1_19_4 = 0x5B, V1_20_2 = 0x5D, V1_20_3 = 0x5F, V1_20_5..V1_21_2 = 0x61], Clientbound, Play, UpdateScore)]
+    UpdateScoreType,
+    #[packet([V1_19_3 = 0x59, V1_19_4 = 0x5D, V1_20_2 = 0x5F, V1_20_3 = 0x61, V1_20_5..V1_21_2 = 0x63], Clientbound, Play, SetSubtitleText)]
+    SetSubtitleTextType,
+    #[packet([V1_19_3 = 0x5B, V1_19_4 = 0x5F, V1_20_2 = 0x61, V1_20_3 = 0x63, V1_20_5..V1_21_2 = 0x65], Clientbound, Play, SetTitleText)]
+    SetTitleTextType,
+    #[packet([V1_19_3 = 0x5C, V1_19_4 = 0x60, V1_20_2 = 0x62, V1_20_3 = 0x64, V1_20_5..V1_21_2 = 0x66], Clientbound, Play, SetTitleAnimationTimes)]
+    SetTitleAnimationTimesType,
+    #[packet([V1_19_3 = 0x5D, V1_19_4 = 0x61, V1_20_2 = 0x63, V1_20_3 = 0x65, V1_20_5..V1_21_2 = 0x67], Clientbound, Play, EntitySoundEffect)]
+    EntitySoundEffectType,
+    #[packet([V1_19_3 = 0x5E, V1_19_4 = 0x62, V1_20_2 = 0x64, V1_20_3 = 0x66, V1_20_5..V1_21_2 = 0x68], Clientbound, Play, SoundEffect)]
+    SoundEffectType,
+    #[packet([V1_19_3 = 0x5F, V1_19_4 = 0x63, V1_20_2 = 0x66, V1_20_3 = 0x68, V1_20_5..V1_21_2 = 0x6A], Clientbound, Play, StopSound)]
+    StopSoundType,
+    #[packet([V1_19_3 = 0x60, V1_19_4 = 0x64, V1_20_2 = 0x67, V1_20_3 = 0x69, V1_20_5..V1_21_2 = 0x6C], Clientbound, Play, SystemChat)]
+    SystemChatType,
+    #[packet([V1_19_3 = 0x61, V1_19_4 = 0x65, V1_20_2 = 0x68, V1_20_3 = 0x6A, V1_20_5..V1_21_2 = 0x6D], Clientbound, Play, SetTabListHeaderAndFooter)]
+    SetTabListHeaderAndFooterType,
+    #[packet([V1_19_3 = 0x64, V1_19_4 = 0x68, V1_20_2 = 0x6B, V1_20_3 = 0x6D, V1_20_5..V1_21_2 = 0x70], Clientbound, Play, TeleportEntity)]
+    TeleportEntityType,
+    #[packet([V1_19_3 = 0x69, V1_19_4 = 0x6D, V1_20_2 = 0x6F, V1_20_3 = 0x73, V1_20_5..V1_21_2 = 0x77], Clientbound, Play, UpdateRecipes)]
+    UpdateRecipesType,
+    #[packet([V1_19_3 = 0x6A, V1_19_4 = 0x6E, V1_20_2 = 0x70, V1_20_3 = 0x74, V1_20_5..V1_21_2 = 0x78], Clientbound, Play, UpdateTags)]
+    UpdateTagsType,
+    #[packet([V1_19_3..V1_21_2 = 0x00], Serverbound, Play, ConfirmTeleportation)]
+    ConfirmTeleportationType,
+    #[packet([V1_19_3..V1_21_2 = 0x03], Serverbound, Play, MessageAcknowledgment)]
+    MessageAcknowledgmentType,
+    #[packet([V1_19_3..V1_21_2 = 0x04], Serverbound, Play, ChatCommand)]
+    ChatCommandType,
+    #[packet([V1_20_5..V1_21_2 = 0x05], Serverbound, Play, SignedChatCommand)]
+    SignedChatCommandType,
+    #[packet([V1_19_3 = 0x05, V1_20_5..V1_21_2 = 0x06], Serverbound, Play, ChatMessage)]
+    ChatMessageType,
+    #[packet([V1_19_3 = 0x0A, V1_19_4 = 0x0B, V1_20_2 = 0x0D, V1_20_5..V1_21_2 = 0x0E], Serverbound, Play, ClickContainer)]
+    ClickContainerType,
+    #[packet([V1_19_3 = 0x0B, V1_19_4 = 0x0C, V1_20_2 = 0x0E, V1_20_5..V1_21_2 = 0x0F], Serverbound, Play, ServerboundCloseContainer)]
+    ServerboundCloseContainerType,
+    #[packet([V1_19_3 = 0x0F, V1_19_4 = 0x10, V1_20_2 = 0x12, V1_20_3 = 0x13, V1_20_5..V1_21_2 = 0x16], Serverbound, Play, InteractEntity)]
+    InteractEntityType,
+    #[packet([V1_19_3 = 0x11, V1_19_4 = 0x12, V1_20_2 = 0x14, V1_20_3 = 0x15, V1_20_5..V1_21_2 = 0x18], Serverbound, Play, ServerboundKeepAlive)]
+    ServerboundKeepAliveType,
+    #[packet([V1_19_3 = 0x13, V1_19_4 = 0x14, V1_20_2 = 0x16, V1_20_3 = 0x17, V1_20_5..V1_21_2 = 0x1A], Serverbound, Play, SetPlayerPosition)]
+    SetPlayerPositionType,
+    #[packet([V1_19_3 = 0x14, V1_19_4 = 0x15, V1_20_2 = 0x17, V1_20_3 = 0x18, V1_20_5..V1_21_2 = 0x1B], Serverbound, Play, SetPlayerPositionAndRotation)]
+    SetPlayerPositionAndRotationType,
+    #[packet([V1_19_3 = 0x15, V1_19_4 = 0x16, V1_20_2 = 0x18, V1_20_3 = 0x19, V1_20_5..V1_21_2 = 0x1C], Serverbound, Play, SetPlayerRotation)]
+    SetPlayerRotationType,
+    #[packet([V1_19_3 = 0x16, V1_19_4 = 0x17, V1_20_2 = 0x19, V1_20_3 = 0x1A, V1_20_5..V1_21_2 = 0x1D], Serverbound, Play, SetPlayerOnGround)]
+    SetPlayerOnGroundType,
+    #[packet([V1_19_3 = 0x1C, V1_19_4 = 0x1D, V1_20_2 = 0x20, V1_20_3 = 0x21, V1_20_5..V1_21_2 = 0x24], Serverbound, Play, PlayerAction)]
+    PlayerActionType,
+    #[packet([V1_19_3 = 0x1F, V1_19_4 = 0x20, V1_20_2 = 0x23, V1_20_3 = 0x24, V1_20_5..V1_21_2 = 0x27], Serverbound, Play, Pong)]
+    PongType,
+    #[packet([V1_19_3 = 0x2F, V1_20_2 = 0x32, V1_20_3 = 0x33, V1_20_5..V1_21_2 = 0x36], Serverbound, Play, SwingArm)]
+    SwingArmType,
+    #[packet([V1_19_3 = 0x31, V1_20_2 = 0x34, V1_20_3 = 0x35, V1_20_5..V1_21_2 = 0x38], Serverbound, Play, UseItemOn)]
+    UseItemOnType,
+    #[packet([V1_19_3 = 0x32, V1_20_2 = 0x35, V1_20_3 = 0x36, V1_20_5..V1_21_2 = 0x39], Serverbound, Play, UseItem)]
+    UseItemType,
 }
 
 impl PacketType {
+    /// Decodes the data of this packet type into its struct, the data must not start with the packet id.
     pub fn wrap_packet<T>(&self, data: &[u8]) -> Result<T, std::io::Error>
     where
         T: Encodable + Decodable,
     {
-        let id = self.get_id();
-        let data = if data[0] != id {
-            [&[id], data].concat()
-        } else {
-            [data].concat()
+        T::decode(&mut Cursor::new(data))
+    }
+}
+
+/// Decodes a packet whose id has already been read from the reader, the rest of the packet is decoded into the
+/// matching struct.
+pub fn decode_packet<R: Read>(
+    id: i32,
+    state: ClientState,
+    direction: PacketDirection,
+    reader: &mut R,
+) -> Result<Packet, std::io::Error> {
+    decode_packet_versioned(ProtocolVersion::LATEST, id, state, direction, reader)
+}
+
+pub fn decode_packet_versioned<R: Read>(
+    version: ProtocolVersion,
+    id: i32,
+    state: ClientState,
+    direction: PacketDirection,
+    reader: &mut R,
+) -> Result<Packet, std::io::Error> {
+    PacketType::get_from_id(id, state, direction, version)?.decode(reader, version)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{animation::EntityAnimationType, player_info::LegacyPlayerInfoAction, VarInt};
+
+    use super::{registry::Packet as _, *};
+
+    #[test]
+    fn ids_depend_on_the_version() {
+        assert_eq!(EntityAnimation::ID, 0x03);
+        assert_eq!(
+            EntityAnimation::id_for(ProtocolVersion::V1_19_3),
+            Some(0x03)
+        );
+        assert_eq!(EntityAnimation::id_for(ProtocolVersion::V1_20), Some(0x04));
+        assert_eq!(EntityAnimation::id_for(ProtocolVersion::V1_21), Some(0x03));
+        assert_eq!(EntityAnimation::id_for(ProtocolVersion::V1_21_2), None);
+        assert_eq!(EntityAnimation::id_for(ProtocolVersion::V1_19_1), None);
+
+        // the packets replaced in 1.19.3 keep their older ids.
+        assert_eq!(LegacyPlayerInfo::id_for(ProtocolVersion::V1_19), Some(0x34));
+        assert_eq!(
+            LegacyPlayerInfo::id_for(ProtocolVersion::V1_19_1),
+            Some(0x37)
+        );
+        assert_eq!(LegacyPlayerInfo::id_for(ProtocolVersion::V1_19_3), None);
+
+        let info = LegacyPlayerInfo {
+            action: LegacyPlayerInfoAction::RemovePlayer(Vec::new()),
+        };
+        let mut buf = Vec::new();
+        info.encode_packet_versioned(&mut buf, ProtocolVersion::V1_19_1)
+            .unwrap();
+        assert_eq!(buf[0], 0x37);
+        assert!(info.encode_packet(&mut Vec::new()).is_err());
+
+        // packets without versions in their ids are the same everywhere.
+        assert_eq!(PingResponse::id_for(ProtocolVersion::V1_19), Some(0x01));
+    }
+
+    #[test]
+    fn decodes_the_packet_of_the_version() {
+        let animation = || EntityAnimation {
+            entity_id: VarInt(1),
+            animation: EntityAnimationType::SwingOffHand,
         };
+        let mut buf = Vec::new();
+        animation().encode(&mut buf).unwrap();
+
+        // 0x03 was Entity Animation in 1.19.3 and Spawn Player from 1.19.4 until 1.20.2.
+        let decoded = decode_packet_versioned(
+            ProtocolVersion::V1_19_3,
+            0x03,
+            ClientState::Play,
+            PacketDirection::Clientbound,
+            &mut &buf[..],
+        )
+        .unwrap();
+        assert_eq!(decoded, Packet::EntityAnimation(animation()));
+
+        assert!(decode_packet_versioned(
+            ProtocolVersion::V1_20,
+            0x03,
+            ClientState::Play,
+            PacketDirection::Clientbound,
+            &mut &buf[..],
+        )
+        .is_err());
+
+        let decoded = decode_packet_versioned(
+            ProtocolVersion::V1_20,
+            0x04,
+            ClientState::Play,
+            PacketDirection::Clientbound,
+            &mut &buf[..],
+        )
+        .unwrap();
+        assert_eq!(decoded, Packet::EntityAnimation(animation()));
+
+        // the play ids of 1.21.2 aren't known.
+        assert!(decode_packet_versioned(
+            ProtocolVersion::V1_21_2,
+            0x03,
+            ClientState::Play,
+            PacketDirection::Clientbound,
+            &mut &buf[..],
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn wraps_data_without_the_id() {
+        let data = [0, 0, 0, 0, 0, 0, 0, 7];
 
-        let reader = &mut Cursor::new(data);
+        let ping = PacketType::PingResponseType
+            .wrap_packet::<PingResponse>(&data)
+            .unwrap();
+        assert_eq!(ping.payload, 7);
+        assert!(PacketType::PingResponseType
+            .wrap_packet::<PingResponse>(&[])
+            .is_err());
 
-        Ok(T::decode(reader)?)
+        let mut buf = Vec::new();
+        Packet::PingResponse(ping).encode(&mut buf).unwrap();
+        assert_eq!(buf[0], 0x01);
+        assert_eq!(buf[1..], data);
     }
 }
@@ -0,0 +1,42 @@
+use std::io::Write;
+
+use types::packet::{ClientState, PacketDirection, ProtocolVersion};
+
+use crate::VarInt;
+
+use super::{Decodable, Encodable, VersionedDecodable, VersionedEncodable};
+
+// implemented by the ParsePacket derive for every struct listed in PacketType, the values come straight from the
+// #[packet(...)] attribute so the struct itself only holds the payload.
+pub trait Packet: Encodable + Decodable + VersionedEncodable + VersionedDecodable {
+    /// The id in the newest protocol version that has this packet.
+    const ID: i32;
+    const STATE: ClientState;
+    const DIRECTION: PacketDirection;
+
+    /// The id in the given protocol version, `None` if the packet doesn't exist in it.
+    fn id_for(_version: ProtocolVersion) -> Option<i32> {
+        Some(Self::ID)
+    }
+
+    /// Writes the packet id followed by the payload.
+    fn encode_packet<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        self.encode_packet_versioned(writer, ProtocolVersion::LATEST)
+    }
+
+    fn encode_packet_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        let id = Self::id_for(version).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                format!("Packet doesn't exist in protocol version {}", version.0),
+            )
+        })?;
+
+        VarInt(id).encode(writer)?;
+        self.encode_versioned(writer, version)
+    }
+}
@@ -0,0 +1,22 @@
+use proc_macros::MinecraftPacket;
+use uuid::Uuid;
+
+use crate::configuration::{KnownPack, ResourcePackResult};
+
+// 0x03
+#[derive(MinecraftPacket, Debug, PartialEq)]
+pub struct AcknowledgeFinishConfiguration {}
+
+// 0x06
+#[derive(MinecraftPacket, Debug, PartialEq)]
+pub struct ResourcePackResponse {
+    #[since(V1_20_3)]
+    pub uuid: Option<Uuid>,
+    pub result: ResourcePackResult,
+}
+
+// 0x07, since 1.20.5. the packs from the server's list that the client has as well.
+#[derive(MinecraftPacket, Debug, PartialEq)]
+pub struct ServerboundKnownPacks {
+    pub known_packs: Vec<KnownPack>,
+}
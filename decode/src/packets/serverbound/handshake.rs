@@ -0,0 +1,65 @@
+use proc_macros::MinecraftPacket;
+
+use crate::{handshake::NextState, VarInt};
+
+// 0x00
+#[derive(MinecraftPacket, Debug, PartialEq)]
+pub struct Handshake {
+    pub protocol_version: VarInt,
+    pub server_address: String,
+    pub server_port: u16,
+    pub next_state: NextState,
+}
+
+#[cfg(test)]
+mod tests {
+    use types::packet::{ClientState, PacketDirection};
+
+    use crate::packets::{decode_packet, Encodable, Packet};
+
+    use super::*;
+
+    #[test]
+    fn handshake() {
+        // protocol 767, localhost:25565 and then login.
+        let buf = [
+            0xFF, 0x05, 9, b'l', b'o', b'c', b'a', b'l', b'h', b'o', b's', b't', 0x63, 0xDD, 2,
+        ];
+        let handshake = Handshake {
+            protocol_version: VarInt(767),
+            server_address: "localhost".into(),
+            server_port: 25565,
+            next_state: NextState::Login,
+        };
+
+        let decoded = decode_packet(
+            0x00,
+            ClientState::Handshake,
+            PacketDirection::Serverbound,
+            &mut &buf[..],
+        )
+        .unwrap();
+        assert_eq!(decoded, Packet::Handshake(handshake));
+
+        let transfer = Handshake {
+            protocol_version: VarInt(767),
+            server_address: "localhost".into(),
+            server_port: 25565,
+            next_state: NextState::Transfer,
+        };
+        let mut encoded = Vec::new();
+        transfer.encode(&mut encoded).unwrap();
+        assert_eq!(encoded[..14], buf[..14]);
+        assert_eq!(encoded[14], 3);
+
+        let mut invalid = buf;
+        invalid[14] = 5;
+        assert!(decode_packet(
+            0x00,
+            ClientState::Handshake,
+            PacketDirection::Serverbound,
+            &mut &invalid[..],
+        )
+        .is_err());
+    }
+}
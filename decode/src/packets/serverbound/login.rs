@@ -1 +1,270 @@
+use std::io::{Read, Write};
 
+use proc_macros::MinecraftPacket;
+use types::packet::ProtocolVersion;
+use uuid::Uuid;
+
+use crate::{
+    chat::PublicKey,
+    datatypes::remaining::RemainingBytes,
+    identifier::Identifier,
+    packets::{required, Decodable, Encodable, VersionedDecodable, VersionedEncodable},
+    VarInt,
+};
+
+// 0x00
+#[derive(Debug, PartialEq)]
+pub struct LoginStart {
+    pub name: String,
+    // the player's chat signing key, only sent before 1.19.3.
+    pub sig_data: Option<PublicKey>,
+    // not sent in 1.19, optional before 1.20.2 and always sent since then.
+    pub uuid: Option<Uuid>,
+}
+
+impl VersionedDecodable for LoginStart {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        let name = String::decode(reader)?;
+
+        let sig_data = if version < ProtocolVersion::V1_19_3 {
+            Option::<PublicKey>::decode(reader)?
+        } else {
+            None
+        };
+
+        let uuid = if version >= ProtocolVersion::V1_20_2 {
+            Some(Uuid::decode(reader)?)
+        } else if version >= ProtocolVersion::V1_19_1 {
+            Option::<Uuid>::decode(reader)?
+        } else {
+            None
+        };
+
+        Ok(LoginStart {
+            name,
+            sig_data,
+            uuid,
+        })
+    }
+}
+
+impl VersionedEncodable for LoginStart {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        self.name.encode(writer)?;
+
+        if version < ProtocolVersion::V1_19_3 {
+            self.sig_data.encode(writer)?;
+        }
+
+        if version < ProtocolVersion::V1_19_1 {
+            return Ok(());
+        }
+
+        if version < ProtocolVersion::V1_20_2 {
+            return self.uuid.encode(writer);
+        }
+
+        required(&self.uuid, "uuid")?.encode(writer)
+    }
+}
+
+impl Decodable for LoginStart {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        Self::decode_versioned(reader, ProtocolVersion::LATEST)
+    }
+}
+
+impl Encodable for LoginStart {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        self.encode_versioned(writer, ProtocolVersion::LATEST)
+    }
+}
+
+// before 1.19.3 a client with a chat signing key could sign the nonce instead of sending back the verify token.
+#[derive(Debug, PartialEq)]
+pub enum EncryptionVerification {
+    VerifyToken(Vec<u8>),
+    Signature { salt: i64, signature: Vec<u8> },
+}
+
+// 0x01
+#[derive(Debug, PartialEq)]
+pub struct EncryptionResponse {
+    pub shared_secret: Vec<u8>,
+    pub verification: EncryptionVerification,
+}
+
+impl VersionedDecodable for EncryptionResponse {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        let shared_secret = Vec::<u8>::decode(reader)?;
+
+        let verification = if version >= ProtocolVersion::V1_19_3 || bool::decode(reader)? {
+            EncryptionVerification::VerifyToken(Vec::<u8>::decode(reader)?)
+        } else {
+            EncryptionVerification::Signature {
+                salt: i64::decode(reader)?,
+                signature: Vec::<u8>::decode(reader)?,
+            }
+        };
+
+        Ok(EncryptionResponse {
+            shared_secret,
+            verification,
+        })
+    }
+}
+
+impl VersionedEncodable for EncryptionResponse {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        self.shared_secret.encode(writer)?;
+
+        match &self.verification {
+            EncryptionVerification::VerifyToken(token) => {
+                if version < ProtocolVersion::V1_19_3 {
+                    true.encode(writer)?;
+                }
+
+                token.encode(writer)
+            }
+            EncryptionVerification::Signature { salt, signature } => {
+                if version >= ProtocolVersion::V1_19_3 {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "Signed nonces were removed in 1.19.3.",
+                    ));
+                }
+
+                false.encode(writer)?;
+                salt.encode(writer)?;
+                signature.encode(writer)
+            }
+        }
+    }
+}
+
+impl Decodable for EncryptionResponse {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        Self::decode_versioned(reader, ProtocolVersion::LATEST)
+    }
+}
+
+impl Encodable for EncryptionResponse {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        self.encode_versioned(writer, ProtocolVersion::LATEST)
+    }
+}
+
+// 0x02, the data is missing when the client didn't understand the request.
+#[derive(MinecraftPacket, Debug, PartialEq)]
+pub struct LoginPluginResponse {
+    pub message_id: VarInt,
+    pub data: Option<RemainingBytes>,
+}
+
+// 0x03, since 1.20.2
+#[derive(MinecraftPacket, Debug, PartialEq)]
+pub struct LoginAcknowledged {}
+
+// 0x04, since 1.20.5
+#[derive(MinecraftPacket, Debug, PartialEq)]
+pub struct CookieResponse {
+    pub key: Identifier,
+    pub payload: Option<Vec<u8>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::packets::registry::Packet as _;
+
+    use super::*;
+
+    fn round_trip(packet: &LoginStart, version: ProtocolVersion) -> Vec<u8> {
+        let mut buf = Vec::new();
+        packet.encode_versioned(&mut buf, version).unwrap();
+
+        let mut reader = &buf[..];
+        assert_eq!(
+            &LoginStart::decode_versioned(&mut reader, version).unwrap(),
+            packet
+        );
+        assert!(reader.is_empty());
+
+        buf
+    }
+
+    #[test]
+    fn login_start_layouts() {
+        let uuid = Uuid::from_bytes([7; 16]);
+        let key = PublicKey {
+            expires_at: 1,
+            key: vec![2],
+            key_signature: vec![3],
+        };
+
+        // 1.19 has the signing key but no uuid.
+        let packet = LoginStart {
+            name: "a".into(),
+            sig_data: Some(key.clone()),
+            uuid: None,
+        };
+        let buf = round_trip(&packet, ProtocolVersion::V1_19);
+        assert_eq!(buf, [1, b'a', 1, 0, 0, 0, 0, 0, 0, 0, 1, 1, 2, 1, 3]);
+
+        // 1.19.1 adds an optional uuid after the key.
+        let packet = LoginStart {
+            uuid: Some(uuid),
+            ..packet
+        };
+        let buf = round_trip(&packet, ProtocolVersion::V1_19_1);
+        assert_eq!(buf[15], 1);
+        assert_eq!(buf[16..], [7; 16]);
+
+        // 1.19.3 drops the key.
+        let packet = LoginStart {
+            name: "a".into(),
+            sig_data: None,
+            uuid: None,
+        };
+        assert_eq!(round_trip(&packet, ProtocolVersion::V1_19_3), [1, b'a', 0]);
+
+        // since 1.20.2 the uuid is always sent.
+        assert!(packet.encode(&mut Vec::new()).is_err());
+        let packet = LoginStart {
+            uuid: Some(uuid),
+            ..packet
+        };
+        assert_eq!(round_trip(&packet, ProtocolVersion::V1_20_2)[2..], [7; 16]);
+    }
+
+    #[test]
+    fn newer_packets_have_no_id_before_they_were_added() {
+        assert_eq!(LoginAcknowledged::ID, 0x03);
+        assert_eq!(LoginAcknowledged::id_for(ProtocolVersion::V1_20), None);
+        assert_eq!(
+            LoginAcknowledged::id_for(ProtocolVersion::V1_20_2),
+            Some(0x03)
+        );
+
+        assert_eq!(CookieResponse::ID, 0x04);
+        assert_eq!(CookieResponse::id_for(ProtocolVersion::V1_20_3), None);
+        assert_eq!(CookieResponse::id_for(ProtocolVersion::V1_21), Some(0x04));
+
+        assert!(LoginAcknowledged {}
+            .encode_packet_versioned(&mut Vec::new(), ProtocolVersion::V1_19_4)
+            .is_err());
+    }
+}
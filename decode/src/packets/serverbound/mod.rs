@@ -1,3 +1,5 @@
+pub mod configuration;
+pub mod handshake;
 pub mod login;
 pub mod play;
 pub mod status;
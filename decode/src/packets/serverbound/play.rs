@@ -1 +1,356 @@
+use std::io::{Read, Write};
 
+use proc_macros::MinecraftPacket;
+use types::packet::ProtocolVersion;
+
+use crate::{
+    chat::{AcknowledgedMessages, ArgumentSignature, MessageSignature},
+    interaction::{BlockFace, Hand, InteractAction, PlayerActionStatus},
+    packets::{Decodable, Encodable, VersionedDecodable, VersionedEncodable},
+    position::{MovementFlags, Position},
+    slot::{ChangedSlot, ClickMode, Slot},
+    VarInt,
+};
+
+// 0x03
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct MessageAcknowledgment {
+    pub message_count: VarInt,
+}
+
+// 0x04, since 1.20.5 signed commands have their own packet.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct ChatCommand {
+    pub command: String,
+    #[until(V1_20_5)]
+    pub timestamp: Option<i64>,
+    #[until(V1_20_5)]
+    pub salt: Option<i64>,
+    #[until(V1_20_5)]
+    pub argument_signatures: Option<Vec<ArgumentSignature>>,
+    #[until(V1_20_5)]
+    pub message_count: Option<VarInt>,
+    #[until(V1_20_5)]
+    pub acknowledged: Option<AcknowledgedMessages>,
+}
+
+// 0x05, since 1.20.5
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct SignedChatCommand {
+    pub command: String,
+    pub timestamp: i64,
+    pub salt: i64,
+    pub argument_signatures: Vec<ArgumentSignature>,
+    pub message_count: VarInt,
+    pub acknowledged: AcknowledgedMessages,
+}
+
+// 0x05
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct ChatMessage {
+    pub message: String,
+    pub timestamp: i64,
+    pub salt: i64,
+    pub signature: Option<MessageSignature>,
+    pub message_count: VarInt,
+    pub acknowledged: AcknowledgedMessages,
+}
+
+// 0x11
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct ServerboundKeepAlive {
+    pub keep_alive_id: i64,
+}
+
+// 0x1F, the answer to a Ping with the same id.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct Pong {
+    pub id: i32,
+}
+
+// 0x0A, the client predicts the result and sends the slots it changed, the server resyncs them if it disagrees.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct ClickContainer {
+    pub window_id: u8,
+    pub state_id: VarInt,
+    // -999 is outside of the window.
+    pub slot: i16,
+    pub button: i8,
+    pub mode: ClickMode,
+    #[versioned]
+    pub changed_slots: Vec<ChangedSlot>,
+    #[versioned]
+    pub carried_item: Slot,
+}
+
+// 0x0B
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct ServerboundCloseContainer {
+    pub window_id: u8,
+}
+
+// 0x00, sent after a Synchronize Player Position with its teleport id.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct ConfirmTeleportation {
+    pub teleport_id: VarInt,
+}
+
+// 0x13
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct SetPlayerPosition {
+    pub x: f64,
+    pub feet_y: f64,
+    pub z: f64,
+    pub flags: MovementFlags,
+}
+
+// 0x14
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct SetPlayerPositionAndRotation {
+    pub x: f64,
+    pub feet_y: f64,
+    pub z: f64,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub flags: MovementFlags,
+}
+
+// 0x15, the rotation is in degrees and the yaw isn't clamped.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct SetPlayerRotation {
+    pub yaw: f32,
+    pub pitch: f32,
+    pub flags: MovementFlags,
+}
+
+// 0x16
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct SetPlayerOnGround {
+    pub flags: MovementFlags,
+}
+
+// 0x1C, the server acknowledges the sequence once it has processed the block changes.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PlayerAction {
+    pub status: PlayerActionStatus,
+    pub location: Position,
+    pub face: BlockFace,
+    pub sequence: VarInt,
+}
+
+// implemented by hand since the face is a byte here.
+impl Decodable for PlayerAction {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        Ok(Self {
+            status: PlayerActionStatus::decode(reader)?,
+            location: Position::decode(reader)?,
+            face: BlockFace::decode_byte(reader)?,
+            sequence: VarInt::decode(reader)?,
+        })
+    }
+}
+
+impl Encodable for PlayerAction {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        self.status.encode(writer)?;
+        self.location.encode(writer)?;
+        self.face.encode_byte(writer)?;
+        self.sequence.encode(writer)
+    }
+}
+
+impl VersionedDecodable for PlayerAction {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        _version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        Self::decode(reader)
+    }
+}
+
+impl VersionedEncodable for PlayerAction {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        _version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        self.encode(writer)
+    }
+}
+
+// 0x31, the cursor is the position on the face that was clicked, from 0 to 1.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct UseItemOn {
+    pub hand: Hand,
+    pub location: Position,
+    pub face: BlockFace,
+    pub cursor_x: f32,
+    pub cursor_y: f32,
+    pub cursor_z: f32,
+    pub inside_block: bool,
+    pub sequence: VarInt,
+}
+
+// 0x32
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct UseItem {
+    pub hand: Hand,
+    pub sequence: VarInt,
+    #[since(V1_21)]
+    pub yaw: Option<f32>,
+    #[since(V1_21)]
+    pub pitch: Option<f32>,
+}
+
+// 0x2F
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct SwingArm {
+    pub hand: Hand,
+}
+
+// 0x0F, also known as Interact.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct InteractEntity {
+    pub entity_id: VarInt,
+    pub action: InteractAction,
+    pub sneaking: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use types::packet::{ClientState, PacketDirection};
+
+    use crate::packets::{decode_packet_versioned, Packet};
+
+    use super::*;
+
+    fn round_trip(packet: Packet, id: i32, version: ProtocolVersion) -> Vec<u8> {
+        let mut buf = Vec::new();
+        packet.encode_versioned(&mut buf, version).unwrap();
+        assert_eq!(buf[0] as i32, id);
+
+        let decoded = decode_packet_versioned(
+            version,
+            id,
+            ClientState::Play,
+            PacketDirection::Serverbound,
+            &mut &buf[1..],
+        )
+        .unwrap();
+        assert_eq!(decoded, packet);
+
+        buf
+    }
+
+    #[test]
+    fn movement() {
+        let flags = MovementFlags { on_ground: true };
+
+        let packet = Packet::SetPlayerPositionAndRotation(SetPlayerPositionAndRotation {
+            x: 1.0,
+            feet_y: 64.0,
+            z: -3.5,
+            yaw: 90.0,
+            pitch: 0.0,
+            flags,
+        });
+        let buf = round_trip(packet, 0x1B, ProtocolVersion::V1_21);
+        assert_eq!(buf.len(), 1 + 24 + 8 + 1);
+        assert_eq!(buf.last(), Some(&1));
+
+        let packet = Packet::SetPlayerOnGround(SetPlayerOnGround { flags });
+        assert_eq!(
+            round_trip(packet, 0x16, ProtocolVersion::V1_19_3),
+            [0x16, 1]
+        );
+    }
+
+    #[test]
+    fn player_action_face_is_a_byte() {
+        let action = PlayerAction {
+            status: PlayerActionStatus::FinishedDigging,
+            location: Position { x: 1, y: 2, z: 3 },
+            face: BlockFace::East,
+            sequence: VarInt(4),
+        };
+        let buf = round_trip(Packet::PlayerAction(action), 0x24, ProtocolVersion::V1_21);
+        assert_eq!(buf[10..], [5, 4]);
+
+        // a byte with the high bit set would continue a VarInt.
+        let mut buf = buf[1..].to_vec();
+        buf[9] = 0x80;
+        assert!(PlayerAction::decode(&mut &buf[..]).is_err());
+
+        let use_item_on = UseItemOn {
+            hand: Hand::MainHand,
+            location: Position { x: 1, y: 2, z: 3 },
+            face: BlockFace::Top,
+            cursor_x: 0.5,
+            cursor_y: 0.5,
+            cursor_z: 1.0,
+            inside_block: false,
+            sequence: VarInt(2),
+        };
+        let buf = round_trip(
+            Packet::UseItemOn(use_item_on),
+            0x31,
+            ProtocolVersion::V1_19_3,
+        );
+        assert_eq!(buf[10], 1);
+    }
+
+    #[test]
+    fn chat_commands_are_signed_in_their_own_packet_since_1_20_5() {
+        let command = ChatCommand {
+            command: "tp".into(),
+            timestamp: Some(1),
+            salt: Some(2),
+            argument_signatures: Some(vec![ArgumentSignature {
+                argument_name: "target".into(),
+                signature: vec![9; 256].into(),
+            }]),
+            message_count: Some(VarInt(0)),
+            acknowledged: Some(vec![0, 0, 1].into()),
+        };
+
+        for (version, id) in [
+            (ProtocolVersion::V1_19_3, 0x04),
+            (ProtocolVersion::V1_20_3, 0x04),
+        ] {
+            let buf = round_trip(Packet::ChatCommand(command.clone()), id, version);
+            assert_eq!(buf.len(), 1 + 3 + 16 + 1 + 7 + 256 + 1 + 3);
+        }
+        assert!(ChatCommand {
+            salt: None,
+            ..command.clone()
+        }
+        .encode_versioned(&mut Vec::new(), ProtocolVersion::V1_20_3)
+        .is_err());
+
+        let unsigned = ChatCommand {
+            command: "tp".into(),
+            timestamp: None,
+            salt: None,
+            argument_signatures: None,
+            message_count: None,
+            acknowledged: None,
+        };
+        let buf = round_trip(Packet::ChatCommand(unsigned), 0x04, ProtocolVersion::V1_21);
+        assert_eq!(buf, [0x04, 2, b't', b'p']);
+
+        let signed = SignedChatCommand {
+            command: "tp".into(),
+            timestamp: 1,
+            salt: 2,
+            argument_signatures: Vec::new(),
+            message_count: VarInt(0),
+            acknowledged: vec![0, 0, 1].into(),
+        };
+        for version in [ProtocolVersion::V1_20_5, ProtocolVersion::V1_21] {
+            round_trip(Packet::SignedChatCommand(signed.clone()), 0x05, version);
+        }
+        assert!(Packet::SignedChatCommand(signed)
+            .encode_versioned(&mut Vec::new(), ProtocolVersion::V1_20_3)
+            .is_err());
+    }
+}
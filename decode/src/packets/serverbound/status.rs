@@ -1 +1,51 @@
+use proc_macros::MinecraftPacket;
 
+// 0x00
+#[derive(MinecraftPacket, Debug, PartialEq)]
+pub struct StatusRequest {}
+
+// 0x01
+#[derive(MinecraftPacket, Debug, PartialEq)]
+pub struct PingRequest {
+    pub payload: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use types::packet::{ClientState, PacketDirection};
+
+    use crate::packets::{
+        clientbound::status::PingResponse, decode_packet, registry::Packet as _, Encodable, Packet,
+    };
+
+    use super::*;
+
+    fn decode(id: i32, direction: PacketDirection, buf: &[u8]) -> Packet {
+        decode_packet(id, ClientState::Status, direction, &mut &buf[..]).unwrap()
+    }
+
+    #[test]
+    fn ping_exchange() {
+        let mut buf = Vec::new();
+        StatusRequest {}.encode_packet(&mut buf).unwrap();
+        assert_eq!(buf, [0x00]);
+        assert_eq!(
+            decode(0x00, PacketDirection::Serverbound, &[]),
+            Packet::StatusRequest(StatusRequest {})
+        );
+
+        let mut buf = Vec::new();
+        PingRequest { payload: 42 }.encode(&mut buf).unwrap();
+        assert_eq!(buf, 42i64.to_be_bytes());
+        assert_eq!(
+            decode(0x01, PacketDirection::Serverbound, &buf),
+            Packet::PingRequest(PingRequest { payload: 42 })
+        );
+
+        // the server answers with the same payload.
+        assert_eq!(
+            decode(0x01, PacketDirection::Clientbound, &buf),
+            Packet::PingResponse(PingResponse { payload: 42 })
+        );
+    }
+}
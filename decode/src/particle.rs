@@ -0,0 +1,559 @@
+use std::io::{Read, Write};
+
+use types::packet::ProtocolVersion;
+
+use crate::{
+    identifier::Identifier,
+    packets::{Decodable, Encodable, VersionedDecodable, VersionedEncodable},
+    position::Position,
+    slot::Slot,
+    VarInt,
+};
+
+// every particle type in registry order, with the version it was added in and the one it was removed in. the id of a
+// particle is its index among the particles that exist in a version.
+const PARTICLES: &[(&str, ProtocolVersion, Option<ProtocolVersion>)] = &[
+    (
+        "minecraft:ambient_entity_effect",
+        ProtocolVersion::V1_19,
+        Some(ProtocolVersion::V1_20_5),
+    ),
+    ("minecraft:angry_villager", ProtocolVersion::V1_19, None),
+    ("minecraft:block", ProtocolVersion::V1_19, None),
+    ("minecraft:block_marker", ProtocolVersion::V1_19, None),
+    ("minecraft:bubble", ProtocolVersion::V1_19, None),
+    ("minecraft:cloud", ProtocolVersion::V1_19, None),
+    ("minecraft:crit", ProtocolVersion::V1_19, None),
+    ("minecraft:damage_indicator", ProtocolVersion::V1_19, None),
+    ("minecraft:dragon_breath", ProtocolVersion::V1_19, None),
+    ("minecraft:dripping_lava", ProtocolVersion::V1_19, None),
+    ("minecraft:falling_lava", ProtocolVersion::V1_19, None),
+    ("minecraft:landing_lava", ProtocolVersion::V1_19, None),
+    ("minecraft:dripping_water", ProtocolVersion::V1_19, None),
+    ("minecraft:falling_water", ProtocolVersion::V1_19, None),
+    ("minecraft:dust", ProtocolVersion::V1_19, None),
+    (
+        "minecraft:dust_color_transition",
+        ProtocolVersion::V1_19,
+        None,
+    ),
+    ("minecraft:effect", ProtocolVersion::V1_19, None),
+    ("minecraft:elder_guardian", ProtocolVersion::V1_19, None),
+    ("minecraft:enchanted_hit", ProtocolVersion::V1_19, None),
+    ("minecraft:enchant", ProtocolVersion::V1_19, None),
+    ("minecraft:end_rod", ProtocolVersion::V1_19, None),
+    ("minecraft:entity_effect", ProtocolVersion::V1_19, None),
+    ("minecraft:explosion_emitter", ProtocolVersion::V1_19, None),
+    ("minecraft:explosion", ProtocolVersion::V1_19, None),
+    ("minecraft:gust", ProtocolVersion::V1_20_3, None),
+    ("minecraft:small_gust", ProtocolVersion::V1_20_5, None),
+    (
+        "minecraft:gust_emitter",
+        ProtocolVersion::V1_20_3,
+        Some(ProtocolVersion::V1_20_5),
+    ),
+    (
+        "minecraft:gust_emitter_large",
+        ProtocolVersion::V1_20_5,
+        None,
+    ),
+    (
+        "minecraft:gust_emitter_small",
+        ProtocolVersion::V1_20_5,
+        None,
+    ),
+    ("minecraft:sonic_boom", ProtocolVersion::V1_19, None),
+    ("minecraft:falling_dust", ProtocolVersion::V1_19, None),
+    ("minecraft:firework", ProtocolVersion::V1_19, None),
+    ("minecraft:fishing", ProtocolVersion::V1_19, None),
+    ("minecraft:flame", ProtocolVersion::V1_19, None),
+    (
+        "minecraft:dripping_cherry_leaves",
+        ProtocolVersion::V1_19_4,
+        Some(ProtocolVersion::V1_20),
+    ),
+    (
+        "minecraft:falling_cherry_leaves",
+        ProtocolVersion::V1_19_4,
+        Some(ProtocolVersion::V1_20),
+    ),
+    (
+        "minecraft:landing_cherry_leaves",
+        ProtocolVersion::V1_19_4,
+        Some(ProtocolVersion::V1_20),
+    ),
+    ("minecraft:infested", ProtocolVersion::V1_20_5, None),
+    ("minecraft:cherry_leaves", ProtocolVersion::V1_20, None),
+    ("minecraft:sculk_soul", ProtocolVersion::V1_19, None),
+    ("minecraft:sculk_charge", ProtocolVersion::V1_19, None),
+    ("minecraft:sculk_charge_pop", ProtocolVersion::V1_19, None),
+    ("minecraft:soul_fire_flame", ProtocolVersion::V1_19, None),
+    ("minecraft:soul", ProtocolVersion::V1_19, None),
+    ("minecraft:flash", ProtocolVersion::V1_19, None),
+    ("minecraft:happy_villager", ProtocolVersion::V1_19, None),
+    ("minecraft:composter", ProtocolVersion::V1_19, None),
+    ("minecraft:heart", ProtocolVersion::V1_19, None),
+    ("minecraft:instant_effect", ProtocolVersion::V1_19, None),
+    ("minecraft:item", ProtocolVersion::V1_19, None),
+    ("minecraft:vibration", ProtocolVersion::V1_19, None),
+    ("minecraft:item_slime", ProtocolVersion::V1_19, None),
+    ("minecraft:item_cobweb", ProtocolVersion::V1_20_5, None),
+    ("minecraft:item_snowball", ProtocolVersion::V1_19, None),
+    ("minecraft:large_smoke", ProtocolVersion::V1_19, None),
+    ("minecraft:lava", ProtocolVersion::V1_19, None),
+    ("minecraft:mycelium", ProtocolVersion::V1_19, None),
+    ("minecraft:note", ProtocolVersion::V1_19, None),
+    ("minecraft:poof", ProtocolVersion::V1_19, None),
+    ("minecraft:portal", ProtocolVersion::V1_19, None),
+    ("minecraft:rain", ProtocolVersion::V1_19, None),
+    ("minecraft:smoke", ProtocolVersion::V1_19, None),
+    ("minecraft:white_smoke", ProtocolVersion::V1_20_3, None),
+    ("minecraft:sneeze", ProtocolVersion::V1_19, None),
+    ("minecraft:spit", ProtocolVersion::V1_19, None),
+    ("minecraft:squid_ink", ProtocolVersion::V1_19, None),
+    ("minecraft:sweep_attack", ProtocolVersion::V1_19, None),
+    ("minecraft:totem_of_undying", ProtocolVersion::V1_19, None),
+    ("minecraft:underwater", ProtocolVersion::V1_19, None),
+    ("minecraft:splash", ProtocolVersion::V1_19, None),
+    ("minecraft:witch", ProtocolVersion::V1_19, None),
+    ("minecraft:bubble_pop", ProtocolVersion::V1_19, None),
+    ("minecraft:current_down", ProtocolVersion::V1_19, None),
+    ("minecraft:bubble_column_up", ProtocolVersion::V1_19, None),
+    ("minecraft:nautilus", ProtocolVersion::V1_19, None),
+    ("minecraft:dolphin", ProtocolVersion::V1_19, None),
+    (
+        "minecraft:campfire_cosy_smoke",
+        ProtocolVersion::V1_19,
+        None,
+    ),
+    (
+        "minecraft:campfire_signal_smoke",
+        ProtocolVersion::V1_19,
+        None,
+    ),
+    ("minecraft:dripping_honey", ProtocolVersion::V1_19, None),
+    ("minecraft:falling_honey", ProtocolVersion::V1_19, None),
+    ("minecraft:landing_honey", ProtocolVersion::V1_19, None),
+    ("minecraft:falling_nectar", ProtocolVersion::V1_19, None),
+    (
+        "minecraft:falling_spore_blossom",
+        ProtocolVersion::V1_19,
+        None,
+    ),
+    ("minecraft:ash", ProtocolVersion::V1_19, None),
+    ("minecraft:crimson_spore", ProtocolVersion::V1_19, None),
+    ("minecraft:warped_spore", ProtocolVersion::V1_19, None),
+    ("minecraft:spore_blossom_air", ProtocolVersion::V1_19, None),
+    (
+        "minecraft:dripping_obsidian_tear",
+        ProtocolVersion::V1_19,
+        None,
+    ),
+    (
+        "minecraft:falling_obsidian_tear",
+        ProtocolVersion::V1_19,
+        None,
+    ),
+    (
+        "minecraft:landing_obsidian_tear",
+        ProtocolVersion::V1_19,
+        None,
+    ),
+    ("minecraft:reverse_portal", ProtocolVersion::V1_19, None),
+    ("minecraft:white_ash", ProtocolVersion::V1_19, None),
+    ("minecraft:small_flame", ProtocolVersion::V1_19, None),
+    ("minecraft:snowflake", ProtocolVersion::V1_19, None),
+    (
+        "minecraft:dripping_dripstone_lava",
+        ProtocolVersion::V1_19,
+        None,
+    ),
+    (
+        "minecraft:falling_dripstone_lava",
+        ProtocolVersion::V1_19,
+        None,
+    ),
+    (
+        "minecraft:dripping_dripstone_water",
+        ProtocolVersion::V1_19,
+        None,
+    ),
+    (
+        "minecraft:falling_dripstone_water",
+        ProtocolVersion::V1_19,
+        None,
+    ),
+    ("minecraft:glow_squid_ink", ProtocolVersion::V1_19, None),
+    ("minecraft:glow", ProtocolVersion::V1_19, None),
+    ("minecraft:wax_on", ProtocolVersion::V1_19, None),
+    ("minecraft:wax_off", ProtocolVersion::V1_19, None),
+    ("minecraft:electric_spark", ProtocolVersion::V1_19, None),
+    ("minecraft:scrape", ProtocolVersion::V1_19, None),
+    ("minecraft:shriek", ProtocolVersion::V1_19, None),
+    ("minecraft:egg_crack", ProtocolVersion::V1_20, None),
+    ("minecraft:dust_plume", ProtocolVersion::V1_20_3, None),
+    (
+        "minecraft:trial_spawner_detection",
+        ProtocolVersion::V1_20_3,
+        None,
+    ),
+    (
+        "minecraft:trial_spawner_detection_ominous",
+        ProtocolVersion::V1_20_5,
+        None,
+    ),
+    ("minecraft:vault_connection", ProtocolVersion::V1_20_5, None),
+    ("minecraft:dust_pillar", ProtocolVersion::V1_20_5, None),
+    ("minecraft:ominous_spawning", ProtocolVersion::V1_20_5, None),
+    ("minecraft:raid_omen", ProtocolVersion::V1_20_5, None),
+    ("minecraft:trial_omen", ProtocolVersion::V1_20_5, None),
+];
+
+// the particles were reordered again in 1.21.2, which this table doesn't know about yet.
+fn particles(version: ProtocolVersion) -> impl Iterator<Item = &'static str> {
+    PARTICLES
+        .iter()
+        .filter(move |(_, since, until)| {
+            version < ProtocolVersion::V1_21_2
+                && version >= *since
+                && until.is_none_or(|until| version < until)
+        })
+        .map(|(name, _, _)| *name)
+}
+
+pub fn particle_name(id: i32, version: ProtocolVersion) -> Option<&'static str> {
+    particles(version).nth(id.try_into().ok()?)
+}
+
+pub fn particle_id(name: &str, version: ProtocolVersion) -> Option<i32> {
+    particles(version)
+        .position(|particle| particle == name)
+        .map(|id| id as i32)
+}
+
+// whether the particle is followed by the data of its type.
+fn has_data(name: &str, version: ProtocolVersion) -> bool {
+    match name {
+        "minecraft:block"
+        | "minecraft:block_marker"
+        | "minecraft:falling_dust"
+        | "minecraft:dust_pillar"
+        | "minecraft:dust"
+        | "minecraft:dust_color_transition"
+        | "minecraft:item"
+        | "minecraft:vibration"
+        | "minecraft:sculk_charge"
+        | "minecraft:shriek" => true,
+        "minecraft:entity_effect" => version >= ProtocolVersion::V1_20_5,
+        _ => false,
+    }
+}
+
+// where a vibration particle travels to.
+#[derive(Debug, PartialEq, Clone)]
+pub enum VibrationSource {
+    Block(Position),
+    Entity { entity_id: VarInt, eye_height: f32 },
+}
+
+// the type was sent by name before 1.20.5, since then it's the id in the position source type registry.
+impl VersionedDecodable for VibrationSource {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        let ty = if version < ProtocolVersion::V1_20_5 {
+            match Identifier::decode(reader)?.to_string().as_str() {
+                "minecraft:block" => 0,
+                "minecraft:entity" => 1,
+                ty => Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("Invalid position source type, {}", ty),
+                ))?,
+            }
+        } else {
+            VarInt::decode(reader)?.0
+        };
+
+        Ok(match ty {
+            0 => Self::Block(Position::decode(reader)?),
+            1 => Self::Entity {
+                entity_id: VarInt::decode(reader)?,
+                eye_height: f32::decode(reader)?,
+            },
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid position source type, {}", ty),
+            ))?,
+        })
+    }
+}
+
+impl VersionedEncodable for VibrationSource {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        let (ty, name) = match self {
+            Self::Block(_) => (0, "minecraft:block"),
+            Self::Entity { .. } => (1, "minecraft:entity"),
+        };
+
+        if version < ProtocolVersion::V1_20_5 {
+            Identifier::from(name).encode(writer)?;
+        } else {
+            VarInt(ty).encode(writer)?;
+        }
+
+        match self {
+            Self::Block(position) => position.encode(writer),
+            Self::Entity {
+                entity_id,
+                eye_height,
+            } => {
+                entity_id.encode(writer)?;
+                eye_height.encode(writer)
+            }
+        }
+    }
+}
+
+// a particle type with its data, the particles without data are only known by their name. colors are from 0 to 1.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ParticleData {
+    Simple(Identifier),
+    Block(VarInt),
+    BlockMarker(VarInt),
+    FallingDust(VarInt),
+    DustPillar(VarInt),
+    Dust {
+        red: f32,
+        green: f32,
+        blue: f32,
+        scale: f32,
+    },
+    DustColorTransition {
+        from_red: f32,
+        from_green: f32,
+        from_blue: f32,
+        to_red: f32,
+        to_green: f32,
+        to_blue: f32,
+        scale: f32,
+    },
+    // the color was added in 1.20.5 as an ARGB int.
+    EntityEffect {
+        color: i32,
+    },
+    Item(Slot),
+    Vibration {
+        destination: VibrationSource,
+        ticks: VarInt,
+    },
+    // the roll is in radians.
+    SculkCharge {
+        roll: f32,
+    },
+    // the delay is in ticks.
+    Shriek {
+        delay: VarInt,
+    },
+}
+
+impl ParticleData {
+    pub fn name(&self) -> String {
+        match self {
+            Self::Simple(name) => name.to_string(),
+            Self::Block(_) => "minecraft:block".to_owned(),
+            Self::BlockMarker(_) => "minecraft:block_marker".to_owned(),
+            Self::FallingDust(_) => "minecraft:falling_dust".to_owned(),
+            Self::DustPillar(_) => "minecraft:dust_pillar".to_owned(),
+            Self::Dust { .. } => "minecraft:dust".to_owned(),
+            Self::DustColorTransition { .. } => "minecraft:dust_color_transition".to_owned(),
+            Self::EntityEffect { .. } => "minecraft:entity_effect".to_owned(),
+            Self::Item(_) => "minecraft:item".to_owned(),
+            Self::Vibration { .. } => "minecraft:vibration".to_owned(),
+            Self::SculkCharge { .. } => "minecraft:sculk_charge".to_owned(),
+            Self::Shriek { .. } => "minecraft:shriek".to_owned(),
+        }
+    }
+
+    // the particle packet sent the id and the data apart from each other before 1.20.5.
+    pub fn decode_data<R: Read>(
+        reader: &mut R,
+        id: VarInt,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        let name = particle_name(id.0, version).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "Unknown particle {} in protocol version {}",
+                    id.0, version.0
+                ),
+            )
+        })?;
+
+        if !has_data(name, version) {
+            return Ok(Self::Simple(Identifier::from(name)));
+        }
+
+        Ok(match name {
+            "minecraft:block" => Self::Block(VarInt::decode(reader)?),
+            "minecraft:block_marker" => Self::BlockMarker(VarInt::decode(reader)?),
+            "minecraft:falling_dust" => Self::FallingDust(VarInt::decode(reader)?),
+            "minecraft:dust_pillar" => Self::DustPillar(VarInt::decode(reader)?),
+            "minecraft:dust" => Self::Dust {
+                red: f32::decode(reader)?,
+                green: f32::decode(reader)?,
+                blue: f32::decode(reader)?,
+                scale: f32::decode(reader)?,
+            },
+            // the scale moved behind both colors in 1.20.5.
+            "minecraft:dust_color_transition" if version < ProtocolVersion::V1_20_5 => {
+                Self::DustColorTransition {
+                    from_red: f32::decode(reader)?,
+                    from_green: f32::decode(reader)?,
+                    from_blue: f32::decode(reader)?,
+                    scale: f32::decode(reader)?,
+                    to_red: f32::decode(reader)?,
+                    to_green: f32::decode(reader)?,
+                    to_blue: f32::decode(reader)?,
+                }
+            }
+            "minecraft:dust_color_transition" => Self::DustColorTransition {
+                from_red: f32::decode(reader)?,
+                from_green: f32::decode(reader)?,
+                from_blue: f32::decode(reader)?,
+                to_red: f32::decode(reader)?,
+                to_green: f32::decode(reader)?,
+                to_blue: f32::decode(reader)?,
+                scale: f32::decode(reader)?,
+            },
+            "minecraft:entity_effect" => Self::EntityEffect {
+                color: i32::decode(reader)?,
+            },
+            "minecraft:item" => Self::Item(Slot::decode_versioned(reader, version)?),
+            "minecraft:vibration" => Self::Vibration {
+                destination: VibrationSource::decode_versioned(reader, version)?,
+                ticks: VarInt::decode(reader)?,
+            },
+            "minecraft:sculk_charge" => Self::SculkCharge {
+                roll: f32::decode(reader)?,
+            },
+            _ => Self::Shriek {
+                delay: VarInt::decode(reader)?,
+            },
+        })
+    }
+
+    pub fn id(&self, version: ProtocolVersion) -> Result<VarInt, std::io::Error> {
+        let name = self.name();
+
+        let id = particle_id(&name, version).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                format!(
+                    "Particle {} doesn't exist in protocol version {}",
+                    name, version.0
+                ),
+            )
+        })?;
+
+        Ok(VarInt(id))
+    }
+
+    pub fn encode_data<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        if matches!(self, Self::Simple(_)) == has_data(&self.name(), version) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "The data of particle {} doesn't match protocol version {}",
+                    self.name(),
+                    version.0
+                ),
+            ));
+        }
+
+        match self {
+            Self::Simple(_) => Ok(()),
+            Self::Block(state)
+            | Self::BlockMarker(state)
+            | Self::FallingDust(state)
+            | Self::DustPillar(state) => state.encode(writer),
+            Self::Dust {
+                red,
+                green,
+                blue,
+                scale,
+            } => {
+                red.encode(writer)?;
+                green.encode(writer)?;
+                blue.encode(writer)?;
+                scale.encode(writer)
+            }
+            Self::DustColorTransition {
+                from_red,
+                from_green,
+                from_blue,
+                to_red,
+                to_green,
+                to_blue,
+                scale,
+            } => {
+                from_red.encode(writer)?;
+                from_green.encode(writer)?;
+                from_blue.encode(writer)?;
+
+                if version < ProtocolVersion::V1_20_5 {
+                    scale.encode(writer)?;
+                }
+
+                to_red.encode(writer)?;
+                to_green.encode(writer)?;
+                to_blue.encode(writer)?;
+
+                if version >= ProtocolVersion::V1_20_5 {
+                    scale.encode(writer)?;
+                }
+
+                Ok(())
+            }
+            Self::EntityEffect { color } => color.encode(writer),
+            Self::Item(item) => item.encode_versioned(writer, version),
+            Self::Vibration { destination, ticks } => {
+                destination.encode_versioned(writer, version)?;
+                ticks.encode(writer)
+            }
+            Self::SculkCharge { roll } => roll.encode(writer),
+            Self::Shriek { delay } => delay.encode(writer),
+        }
+    }
+}
+
+impl VersionedDecodable for ParticleData {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        let id = VarInt::decode(reader)?;
+
+        Self::decode_data(reader, id, version)
+    }
+}
+
+impl VersionedEncodable for ParticleData {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        self.id(version)?.encode(writer)?;
+        self.encode_data(writer, version)
+    }
+}
@@ -0,0 +1,378 @@
+use proc_macros::MinecraftPacket;
+use types::packet::ProtocolVersion;
+use uuid::Uuid;
+
+use crate::{
+    chat::{ChatSession, PublicKey},
+    packets::{required, Decodable, Encodable, VersionedDecodable, VersionedEncodable},
+    profile::ProfileProperty,
+    text::TextComponent,
+    VarInt,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayerInfoAction {
+    AddPlayer = 0,
+    InitializeChat = 1,
+    UpdateGameMode = 2,
+    UpdateListed = 3,
+    UpdateLatency = 4,
+    UpdateDisplayName = 5,
+    // since 1.21.2
+    UpdateListOrder = 6,
+}
+
+// the actions of a Player Info Update, sent as a single byte with one bit per action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PlayerInfoActions(pub u8);
+
+impl PlayerInfoActions {
+    pub fn contains(&self, action: PlayerInfoAction) -> bool {
+        self.0 & (1 << action as u8) != 0
+    }
+
+    pub fn insert(&mut self, action: PlayerInfoAction) {
+        self.0 |= 1 << action as u8;
+    }
+
+    pub fn with(mut self, action: PlayerInfoAction) -> Self {
+        self.insert(action);
+        self
+    }
+}
+
+impl Decodable for PlayerInfoActions {
+    fn decode<R: std::io::Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        Ok(PlayerInfoActions(u8::decode(reader)?))
+    }
+}
+
+impl Encodable for PlayerInfoActions {
+    fn encode<W: std::io::Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        self.0.encode(writer)
+    }
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct PlayerProfile {
+    pub name: String,
+    pub properties: Vec<ProfileProperty>,
+}
+
+// only the fields of the actions in the packet are sent, the others are None. the chat session and display name can
+// also be sent without a value, to remove them.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct PlayerInfoEntry {
+    pub uuid: Uuid,
+    pub profile: Option<PlayerProfile>,
+    pub chat_session: Option<Option<ChatSession>>,
+    pub game_mode: Option<VarInt>,
+    pub listed: Option<bool>,
+    pub latency: Option<VarInt>,
+    pub display_name: Option<Option<TextComponent>>,
+    pub list_order: Option<VarInt>,
+}
+
+fn check_list_order(
+    version: ProtocolVersion,
+    kind: std::io::ErrorKind,
+) -> Result<(), std::io::Error> {
+    if version < ProtocolVersion::V1_21_2 {
+        return Err(std::io::Error::new(
+            kind,
+            "The list order was added in 1.21.2.",
+        ));
+    }
+
+    Ok(())
+}
+
+impl PlayerInfoEntry {
+    pub fn decode_actions<R: std::io::Read>(
+        reader: &mut R,
+        actions: PlayerInfoActions,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        let mut entry = PlayerInfoEntry {
+            uuid: Uuid::decode(reader)?,
+            ..Default::default()
+        };
+
+        if actions.contains(PlayerInfoAction::AddPlayer) {
+            entry.profile = Some(PlayerProfile::decode(reader)?);
+        }
+
+        if actions.contains(PlayerInfoAction::InitializeChat) {
+            entry.chat_session = Some(Option::decode(reader)?);
+        }
+
+        if actions.contains(PlayerInfoAction::UpdateGameMode) {
+            entry.game_mode = Some(VarInt::decode(reader)?);
+        }
+
+        if actions.contains(PlayerInfoAction::UpdateListed) {
+            entry.listed = Some(bool::decode(reader)?);
+        }
+
+        if actions.contains(PlayerInfoAction::UpdateLatency) {
+            entry.latency = Some(VarInt::decode(reader)?);
+        }
+
+        if actions.contains(PlayerInfoAction::UpdateDisplayName) {
+            entry.display_name = Some(Option::decode_versioned(reader, version)?);
+        }
+
+        if actions.contains(PlayerInfoAction::UpdateListOrder) {
+            check_list_order(version, std::io::ErrorKind::InvalidData)?;
+            entry.list_order = Some(VarInt::decode(reader)?);
+        }
+
+        Ok(entry)
+    }
+
+    pub fn encode_actions<W: std::io::Write>(
+        &self,
+        writer: &mut W,
+        actions: PlayerInfoActions,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        self.uuid.encode(writer)?;
+
+        if actions.contains(PlayerInfoAction::AddPlayer) {
+            required(&self.profile, "profile")?.encode(writer)?;
+        }
+
+        if actions.contains(PlayerInfoAction::InitializeChat) {
+            required(&self.chat_session, "chat_session")?.encode(writer)?;
+        }
+
+        if actions.contains(PlayerInfoAction::UpdateGameMode) {
+            required(&self.game_mode, "game_mode")?.encode(writer)?;
+        }
+
+        if actions.contains(PlayerInfoAction::UpdateListed) {
+            required(&self.listed, "listed")?.encode(writer)?;
+        }
+
+        if actions.contains(PlayerInfoAction::UpdateLatency) {
+            required(&self.latency, "latency")?.encode(writer)?;
+        }
+
+        if actions.contains(PlayerInfoAction::UpdateDisplayName) {
+            required(&self.display_name, "display_name")?.encode_versioned(writer, version)?;
+        }
+
+        if actions.contains(PlayerInfoAction::UpdateListOrder) {
+            check_list_order(version, std::io::ErrorKind::InvalidInput)?;
+            required(&self.list_order, "list_order")?.encode(writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct LegacyPlayerEntry {
+    pub uuid: Uuid,
+    pub name: String,
+    pub properties: Vec<ProfileProperty>,
+    pub game_mode: VarInt,
+    pub latency: VarInt,
+    pub display_name: Option<TextComponent>,
+    pub public_key: Option<PublicKey>,
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct LegacyGameMode {
+    pub uuid: Uuid,
+    pub game_mode: VarInt,
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct LegacyLatency {
+    pub uuid: Uuid,
+    pub latency: VarInt,
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct LegacyDisplayName {
+    pub uuid: Uuid,
+    pub display_name: Option<TextComponent>,
+}
+
+// before 1.19.3 every packet had a single action, sent as a VarInt, and players were removed with it as well.
+#[derive(Debug, PartialEq, Clone)]
+pub enum LegacyPlayerInfoAction {
+    AddPlayer(Vec<LegacyPlayerEntry>),
+    UpdateGameMode(Vec<LegacyGameMode>),
+    UpdateLatency(Vec<LegacyLatency>),
+    UpdateDisplayName(Vec<LegacyDisplayName>),
+    RemovePlayer(Vec<Uuid>),
+}
+
+impl Decodable for LegacyPlayerInfoAction {
+    fn decode<R: std::io::Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let action = VarInt::decode(reader)?;
+
+        Ok(match action.0 {
+            0 => Self::AddPlayer(Vec::decode(reader)?),
+            1 => Self::UpdateGameMode(Vec::decode(reader)?),
+            2 => Self::UpdateLatency(Vec::decode(reader)?),
+            3 => Self::UpdateDisplayName(Vec::decode(reader)?),
+            4 => Self::RemovePlayer(Vec::decode(reader)?),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid player info action, {}", action.0),
+            ))?,
+        })
+    }
+}
+
+impl Encodable for LegacyPlayerInfoAction {
+    fn encode<W: std::io::Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        match self {
+            Self::AddPlayer(entries) => {
+                VarInt(0).encode(writer)?;
+                entries.encode(writer)
+            }
+            Self::UpdateGameMode(entries) => {
+                VarInt(1).encode(writer)?;
+                entries.encode(writer)
+            }
+            Self::UpdateLatency(entries) => {
+                VarInt(2).encode(writer)?;
+                entries.encode(writer)
+            }
+            Self::UpdateDisplayName(entries) => {
+                VarInt(3).encode(writer)?;
+                entries.encode(writer)
+            }
+            Self::RemovePlayer(uuids) => {
+                VarInt(4).encode(writer)?;
+                uuids.encode(writer)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry() -> PlayerInfoEntry {
+        PlayerInfoEntry {
+            uuid: Uuid::from_bytes([1; 16]),
+            profile: Some(PlayerProfile {
+                name: "Steve".into(),
+                properties: Vec::new(),
+            }),
+            chat_session: Some(Some(ChatSession {
+                session_id: Uuid::nil(),
+                public_key: PublicKey {
+                    expires_at: 5,
+                    key: vec![1],
+                    key_signature: vec![2],
+                },
+            })),
+            listed: Some(true),
+            display_name: Some(Some("Steve".into())),
+            list_order: Some(VarInt(3)),
+            ..Default::default()
+        }
+    }
+
+    fn round_trip(
+        entry: &PlayerInfoEntry,
+        actions: PlayerInfoActions,
+        version: ProtocolVersion,
+    ) -> PlayerInfoEntry {
+        let mut buf = Vec::new();
+        entry.encode_actions(&mut buf, actions, version).unwrap();
+
+        let mut reader = &buf[..];
+        let decoded = PlayerInfoEntry::decode_actions(&mut reader, actions, version).unwrap();
+        assert!(reader.is_empty());
+        decoded
+    }
+
+    #[test]
+    fn entries_only_have_the_fields_of_their_actions() {
+        use PlayerInfoAction::*;
+
+        let actions = PlayerInfoActions::default()
+            .with(AddPlayer)
+            .with(InitializeChat)
+            .with(UpdateListed)
+            .with(UpdateDisplayName);
+        assert_eq!(actions.0, 0b101011);
+
+        let expected = PlayerInfoEntry {
+            list_order: None,
+            ..entry()
+        };
+        for version in [ProtocolVersion::V1_19_3, ProtocolVersion::V1_21] {
+            assert_eq!(round_trip(&entry(), actions, version), expected);
+        }
+
+        // the fields of every action have to be there.
+        assert!(entry()
+            .encode_actions(
+                &mut Vec::new(),
+                actions.with(UpdateLatency),
+                ProtocolVersion::V1_21
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn list_order_was_added_in_1_21_2() {
+        let actions = PlayerInfoActions::default().with(PlayerInfoAction::UpdateListOrder);
+        let expected = PlayerInfoEntry {
+            uuid: Uuid::from_bytes([1; 16]),
+            list_order: Some(VarInt(3)),
+            ..Default::default()
+        };
+        assert_eq!(
+            round_trip(&entry(), actions, ProtocolVersion::V1_21_2),
+            expected
+        );
+
+        assert!(entry()
+            .encode_actions(&mut Vec::new(), actions, ProtocolVersion::V1_21)
+            .is_err());
+
+        let mut buf = Vec::new();
+        entry()
+            .encode_actions(&mut buf, actions, ProtocolVersion::V1_21_2)
+            .unwrap();
+        assert!(
+            PlayerInfoEntry::decode_actions(&mut &buf[..], actions, ProtocolVersion::V1_21)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn legacy_actions() {
+        let add = LegacyPlayerInfoAction::AddPlayer(vec![LegacyPlayerEntry {
+            uuid: Uuid::nil(),
+            name: "a".into(),
+            properties: Vec::new(),
+            game_mode: VarInt(0),
+            latency: VarInt(20),
+            display_name: Some("x".into()),
+            public_key: None,
+        }]);
+        let remove = LegacyPlayerInfoAction::RemovePlayer(vec![Uuid::nil()]);
+
+        for action in [add, remove] {
+            let mut buf = Vec::new();
+            action.encode(&mut buf).unwrap();
+            assert_eq!(
+                LegacyPlayerInfoAction::decode(&mut &buf[..]).unwrap(),
+                action
+            );
+        }
+
+        assert!(LegacyPlayerInfoAction::decode(&mut &[5u8, 0][..]).is_err());
+    }
+}
@@ -1,12 +1,73 @@
 use std::{io::Write, num::TryFromIntError};
 
 use byteorder::{BigEndian, ReadBytesExt};
+use proc_macros::MinecraftPacket;
 
-use crate::packets::{Decodable, Encodable};
+use crate::{
+    identifier::Identifier,
+    packets::{Decodable, Encodable},
+};
 
-pub type Angle = f32;
+// a rotation in steps of 1/256 of a full turn, sent as a single byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Angle(pub u8);
 
-#[derive(Debug, Clone, Copy)]
+impl Angle {
+    pub fn from_degrees(degrees: f32) -> Self {
+        Angle((degrees.rem_euclid(360.0) / 360.0 * 256.0).round() as i32 as u8)
+    }
+
+    pub fn degrees(&self) -> f32 {
+        self.0 as f32 * 360.0 / 256.0
+    }
+}
+
+impl Decodable for Angle {
+    fn decode<R: std::io::Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        Ok(Angle(reader.read_u8()?))
+    }
+}
+
+impl Encodable for Angle {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        writer.write_all(&[self.0])
+    }
+}
+
+// the change of a coordinate in 1/4096 of a block, so an entity can move at most 8 blocks per packet this way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PositionDelta(pub i16);
+
+impl PositionDelta {
+    /// `None` when the distance is too far for a delta, a teleport has to be sent instead.
+    pub fn between(from: f64, to: f64) -> Option<Self> {
+        let delta = (to * 4096.0).round() - (from * 4096.0).round();
+
+        if delta < i16::MIN as f64 || delta > i16::MAX as f64 {
+            return None;
+        }
+
+        Some(PositionDelta(delta as i16))
+    }
+
+    pub fn blocks(&self) -> f64 {
+        self.0 as f64 / 4096.0
+    }
+}
+
+impl Decodable for PositionDelta {
+    fn decode<R: std::io::Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        Ok(PositionDelta(reader.read_i16::<BigEndian>()?))
+    }
+}
+
+impl Encodable for PositionDelta {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        writer.write_all(&self.0.to_be_bytes())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Position {
     pub x: i32,
     pub y: i16,
@@ -36,12 +97,103 @@ impl Decodable for Position {
 
 impl Encodable for Position {
     fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
-        let value: i64 = (((self.x & 0x3FFFFFF) as i64) << 38) as i64
-            | ((self.z & 0x3FFFFFF) << 12) as i64
-            | (self.y as i32 & 0xFFF) as i64;
+        let value: i64 = ((self.x as i64 & 0x3FFFFFF) << 38)
+            | ((self.z as i64 & 0x3FFFFFF) << 12)
+            | (self.y as i64 & 0xFFF);
 
         let buf = value.to_be_bytes();
 
         writer.write_all(&buf)
     }
 }
+
+// a block position together with the dimension it's in.
+#[derive(MinecraftPacket, Debug, Clone, PartialEq, Eq)]
+pub struct GlobalPosition {
+    pub dimension: Identifier,
+    pub location: Position,
+}
+
+// the position of a 16x16x16 chunk section, packed into a long like a block position but with 22 bits for x and z.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SectionPosition {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Decodable for SectionPosition {
+    fn decode<R: std::io::Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let value = reader.read_i64::<BigEndian>()?;
+
+        Ok(SectionPosition {
+            x: (value >> 42) as i32,
+            y: (value << 44 >> 44) as i32,
+            z: (value << 22 >> 42) as i32,
+        })
+    }
+}
+
+impl Encodable for SectionPosition {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        let value: i64 = ((self.x as i64 & 0x3FFFFF) << 42)
+            | ((self.z as i64 & 0x3FFFFF) << 20)
+            | (self.y as i64 & 0xFFFFF);
+
+        writer.write_all(&value.to_be_bytes())
+    }
+}
+
+// sent with every player movement. 1.21.2 turns this into a byte of flags, which can be read here once the play
+// ids of that version are known.
+#[derive(MinecraftPacket, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MovementFlags {
+    pub on_ground: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn angles() {
+        assert_eq!(Angle::from_degrees(90.0), Angle(64));
+        assert_eq!(Angle::from_degrees(-90.0), Angle(192));
+        assert_eq!(Angle::from_degrees(360.0), Angle(0));
+        assert_eq!(Angle(128).degrees(), 180.0);
+
+        let mut buf = Vec::new();
+        Angle(200).encode(&mut buf).unwrap();
+        assert_eq!(buf, [200]);
+        assert_eq!(Angle::decode(&mut &buf[..]).unwrap(), Angle(200));
+    }
+
+    #[test]
+    fn position_deltas() {
+        assert_eq!(PositionDelta::between(1.0, 2.5), Some(PositionDelta(6144)));
+        assert_eq!(PositionDelta::between(2.5, 1.0), Some(PositionDelta(-6144)));
+        assert_eq!(
+            PositionDelta::between(0.0, 7.99),
+            Some(PositionDelta(32727))
+        );
+        assert_eq!(PositionDelta::between(0.0, 9.0), None);
+        assert_eq!(PositionDelta::between(9.0, 0.0), None);
+        assert_eq!(PositionDelta(-2048).blocks(), -0.5);
+
+        let mut buf = Vec::new();
+        PositionDelta(-1).encode(&mut buf).unwrap();
+        PositionDelta(0x18).encode(&mut buf).unwrap();
+        assert_eq!(buf, [0xFF, 0xFF, 0, 0x18]);
+
+        let mut reader = &buf[..];
+        assert_eq!(
+            PositionDelta::decode(&mut reader).unwrap(),
+            PositionDelta(-1)
+        );
+        assert_eq!(
+            PositionDelta::decode(&mut reader).unwrap(),
+            PositionDelta(0x18)
+        );
+        assert!(reader.is_empty());
+    }
+}
@@ -0,0 +1,9 @@
+use proc_macros::MinecraftPacket;
+
+// a property of a player's game profile, most importantly the signed "textures" property with their skin.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct ProfileProperty {
+    pub name: String,
+    pub value: String,
+    pub signature: Option<String>,
+}
@@ -0,0 +1,381 @@
+use std::io::{Read, Write};
+
+use types::packet::ProtocolVersion;
+
+use crate::{
+    identifier::Identifier,
+    packets::{Decodable, Encodable, VersionedDecodable, VersionedEncodable},
+    slot::Slot,
+    VarInt,
+};
+
+// since 1.20.5 the recipe type is sent as its id in the recipe serializer registry.
+const RECIPE_SERIALIZERS: &[&str] = &[
+    "minecraft:crafting_shaped",
+    "minecraft:crafting_shapeless",
+    "minecraft:crafting_special_armordye",
+    "minecraft:crafting_special_bookcloning",
+    "minecraft:crafting_special_mapcloning",
+    "minecraft:crafting_special_mapextending",
+    "minecraft:crafting_special_firework_rocket",
+    "minecraft:crafting_special_firework_star",
+    "minecraft:crafting_special_firework_star_fade",
+    "minecraft:crafting_special_tippedarrow",
+    "minecraft:crafting_special_bannerduplicate",
+    "minecraft:crafting_special_shielddecoration",
+    "minecraft:crafting_special_shulkerboxcoloring",
+    "minecraft:crafting_special_suspiciousstew",
+    "minecraft:crafting_special_repairitem",
+    "minecraft:smelting",
+    "minecraft:blasting",
+    "minecraft:smoking",
+    "minecraft:campfire_cooking",
+    "minecraft:stonecutting",
+    "minecraft:smithing_transform",
+    "minecraft:smithing_trim",
+    "minecraft:crafting_decorated_pot",
+];
+
+// any of the items fits into the slot.
+pub type Ingredient = Vec<Slot>;
+
+// the categories are ids of the recipe book tabs, e.g. building or redstone for crafting.
+#[derive(Debug, PartialEq, Clone)]
+pub enum RecipeData {
+    Shaped {
+        group: String,
+        category: VarInt,
+        width: VarInt,
+        height: VarInt,
+        // row by row, width * height of them.
+        ingredients: Vec<Ingredient>,
+        result: Slot,
+        // added in 1.19.4, it was always shown before.
+        show_notification: bool,
+    },
+    Shapeless {
+        group: String,
+        category: VarInt,
+        ingredients: Vec<Ingredient>,
+        result: Slot,
+    },
+    // the crafting_special_* recipes and the decorated pot, which are implemented by the client itself.
+    Special {
+        category: VarInt,
+    },
+    // smelting, blasting, smoking and campfire cooking.
+    Cooking {
+        group: String,
+        category: VarInt,
+        ingredient: Ingredient,
+        result: Slot,
+        experience: f32,
+        cooking_time: VarInt,
+    },
+    Stonecutting {
+        group: String,
+        ingredient: Ingredient,
+        result: Slot,
+    },
+    SmithingTransform {
+        template: Ingredient,
+        base: Ingredient,
+        addition: Ingredient,
+        result: Slot,
+    },
+    SmithingTrim {
+        template: Ingredient,
+        base: Ingredient,
+        addition: Ingredient,
+    },
+    // the smithing table before 1.20, without a template.
+    LegacySmithing {
+        base: Ingredient,
+        addition: Ingredient,
+        result: Slot,
+    },
+}
+
+impl RecipeData {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        ty: &Identifier,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        let ty = ty.to_string();
+
+        Ok(match ty.as_str() {
+            "minecraft:crafting_shaped" if version < ProtocolVersion::V1_20_3 => {
+                let width = VarInt::decode(reader)?;
+                let height = VarInt::decode(reader)?;
+                let group = String::decode(reader)?;
+                let category = VarInt::decode(reader)?;
+                let ingredients = decode_grid(reader, &width, &height, version)?;
+                let result = Slot::decode_versioned(reader, version)?;
+                let show_notification = version < ProtocolVersion::V1_19_4 || bool::decode(reader)?;
+
+                Self::Shaped {
+                    group,
+                    category,
+                    width,
+                    height,
+                    ingredients,
+                    result,
+                    show_notification,
+                }
+            }
+            "minecraft:crafting_shaped" => {
+                let group = String::decode(reader)?;
+                let category = VarInt::decode(reader)?;
+                let width = VarInt::decode(reader)?;
+                let height = VarInt::decode(reader)?;
+
+                Self::Shaped {
+                    group,
+                    category,
+                    ingredients: decode_grid(reader, &width, &height, version)?,
+                    width,
+                    height,
+                    result: Slot::decode_versioned(reader, version)?,
+                    show_notification: bool::decode(reader)?,
+                }
+            }
+            "minecraft:crafting_shapeless" => Self::Shapeless {
+                group: String::decode(reader)?,
+                category: VarInt::decode(reader)?,
+                ingredients: Vec::<Ingredient>::decode_versioned(reader, version)?,
+                result: Slot::decode_versioned(reader, version)?,
+            },
+            "minecraft:smelting"
+            | "minecraft:blasting"
+            | "minecraft:smoking"
+            | "minecraft:campfire_cooking" => Self::Cooking {
+                group: String::decode(reader)?,
+                category: VarInt::decode(reader)?,
+                ingredient: Ingredient::decode_versioned(reader, version)?,
+                result: Slot::decode_versioned(reader, version)?,
+                experience: f32::decode(reader)?,
+                cooking_time: VarInt::decode(reader)?,
+            },
+            "minecraft:stonecutting" => Self::Stonecutting {
+                group: String::decode(reader)?,
+                ingredient: Ingredient::decode_versioned(reader, version)?,
+                result: Slot::decode_versioned(reader, version)?,
+            },
+            "minecraft:smithing_transform" => Self::SmithingTransform {
+                template: Ingredient::decode_versioned(reader, version)?,
+                base: Ingredient::decode_versioned(reader, version)?,
+                addition: Ingredient::decode_versioned(reader, version)?,
+                result: Slot::decode_versioned(reader, version)?,
+            },
+            "minecraft:smithing_trim" => Self::SmithingTrim {
+                template: Ingredient::decode_versioned(reader, version)?,
+                base: Ingredient::decode_versioned(reader, version)?,
+                addition: Ingredient::decode_versioned(reader, version)?,
+            },
+            "minecraft:smithing" => Self::LegacySmithing {
+                base: Ingredient::decode_versioned(reader, version)?,
+                addition: Ingredient::decode_versioned(reader, version)?,
+                result: Slot::decode_versioned(reader, version)?,
+            },
+            _ if ty.starts_with("minecraft:crafting_special_")
+                || ty == "minecraft:crafting_decorated_pot" =>
+            {
+                Self::Special {
+                    category: VarInt::decode(reader)?,
+                }
+            }
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                format!("Unknown recipe type, {}", ty),
+            ))?,
+        })
+    }
+
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        match self {
+            Self::Shaped {
+                group,
+                category,
+                width,
+                height,
+                ingredients,
+                result,
+                show_notification,
+            } => {
+                if version < ProtocolVersion::V1_20_3 {
+                    width.encode(writer)?;
+                    height.encode(writer)?;
+                    group.encode(writer)?;
+                    category.encode(writer)?;
+                } else {
+                    group.encode(writer)?;
+                    category.encode(writer)?;
+                    width.encode(writer)?;
+                    height.encode(writer)?;
+                }
+
+                for ingredient in ingredients {
+                    ingredient.encode_versioned(writer, version)?;
+                }
+                result.encode_versioned(writer, version)?;
+
+                if version >= ProtocolVersion::V1_19_4 {
+                    show_notification.encode(writer)?;
+                }
+
+                Ok(())
+            }
+            Self::Shapeless {
+                group,
+                category,
+                ingredients,
+                result,
+            } => {
+                group.encode(writer)?;
+                category.encode(writer)?;
+                ingredients.encode_versioned(writer, version)?;
+                result.encode_versioned(writer, version)
+            }
+            Self::Special { category } => category.encode(writer),
+            Self::Cooking {
+                group,
+                category,
+                ingredient,
+                result,
+                experience,
+                cooking_time,
+            } => {
+                group.encode(writer)?;
+                category.encode(writer)?;
+                ingredient.encode_versioned(writer, version)?;
+                result.encode_versioned(writer, version)?;
+                experience.encode(writer)?;
+                cooking_time.encode(writer)
+            }
+            Self::Stonecutting {
+                group,
+                ingredient,
+                result,
+            } => {
+                group.encode(writer)?;
+                ingredient.encode_versioned(writer, version)?;
+                result.encode_versioned(writer, version)
+            }
+            Self::SmithingTransform {
+                template,
+                base,
+                addition,
+                result,
+            } => {
+                template.encode_versioned(writer, version)?;
+                base.encode_versioned(writer, version)?;
+                addition.encode_versioned(writer, version)?;
+                result.encode_versioned(writer, version)
+            }
+            Self::SmithingTrim {
+                template,
+                base,
+                addition,
+            } => {
+                template.encode_versioned(writer, version)?;
+                base.encode_versioned(writer, version)?;
+                addition.encode_versioned(writer, version)
+            }
+            Self::LegacySmithing {
+                base,
+                addition,
+                result,
+            } => {
+                base.encode_versioned(writer, version)?;
+                addition.encode_versioned(writer, version)?;
+                result.encode_versioned(writer, version)
+            }
+        }
+    }
+}
+
+// the ingredients of a shaped recipe aren't prefixed with their length.
+fn decode_grid<R: Read>(
+    reader: &mut R,
+    width: &VarInt,
+    height: &VarInt,
+    version: ProtocolVersion,
+) -> Result<Vec<Ingredient>, std::io::Error> {
+    (0..width.0 * height.0)
+        .map(|_| Ingredient::decode_versioned(reader, version))
+        .collect()
+}
+
+// a recipe as sent in Update Recipes before 1.21.2. the type decides the layout of the data.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Recipe {
+    pub id: Identifier,
+    pub ty: Identifier,
+    pub data: RecipeData,
+}
+
+impl VersionedDecodable for Recipe {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        let (id, ty) = if version < ProtocolVersion::V1_20_5 {
+            let ty = Identifier::decode(reader)?;
+            (Identifier::decode(reader)?, ty)
+        } else {
+            let id = Identifier::decode(reader)?;
+            let serializer = VarInt::decode(reader)?.0;
+
+            let ty = usize::try_from(serializer)
+                .ok()
+                .and_then(|serializer| RECIPE_SERIALIZERS.get(serializer))
+                .ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!("Unknown recipe serializer, {}", serializer),
+                    )
+                })?;
+
+            (id, Identifier::from(*ty))
+        };
+
+        let data = RecipeData::decode_versioned(reader, &ty, version)?;
+
+        Ok(Recipe { id, ty, data })
+    }
+}
+
+impl VersionedEncodable for Recipe {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        if version < ProtocolVersion::V1_20_5 {
+            self.ty.encode(writer)?;
+            self.id.encode(writer)?;
+        } else {
+            let ty = self.ty.to_string();
+
+            let serializer = RECIPE_SERIALIZERS
+                .iter()
+                .position(|serializer| *serializer == ty)
+                .ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        format!("Unknown recipe serializer, {}", ty),
+                    )
+                })?;
+
+            self.id.encode(writer)?;
+            VarInt(serializer as i32).encode(writer)?;
+        }
+
+        self.data.encode_versioned(writer, version)
+    }
+}
@@ -0,0 +1,286 @@
+use std::io::{Read, Write};
+
+use proc_macros::MinecraftPacket;
+use types::packet::ProtocolVersion;
+
+use crate::{
+    nbt,
+    packets::{Decodable, Encodable, VersionedDecodable, VersionedEncodable},
+    text::TextComponent,
+    VarInt,
+};
+
+// how the scores are shown next to their names since 1.20.3, the styling is a compound like a text component's style.
+#[derive(Debug, PartialEq, Clone)]
+pub enum NumberFormat {
+    Blank,
+    Styled(nbt::Value),
+    Fixed(Box<TextComponent>),
+}
+
+impl VersionedDecodable for NumberFormat {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        let ty = VarInt::decode(reader)?;
+
+        Ok(match ty.0 {
+            0 => Self::Blank,
+            1 => Self::Styled(nbt::Value::decode_versioned(reader, version)?),
+            2 => Self::Fixed(Box::new(TextComponent::decode_versioned(reader, version)?)),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid number format, {}", ty.0),
+            ))?,
+        })
+    }
+}
+
+impl VersionedEncodable for NumberFormat {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        match self {
+            Self::Blank => VarInt(0).encode(writer),
+            Self::Styled(style) => {
+                VarInt(1).encode(writer)?;
+                style.encode_versioned(writer, version)
+            }
+            Self::Fixed(content) => {
+                VarInt(2).encode(writer)?;
+                content.encode_versioned(writer, version)
+            }
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ObjectiveRenderType {
+    Integer = 0,
+    Hearts = 1,
+}
+
+impl Decodable for ObjectiveRenderType {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let ty = VarInt::decode(reader)?;
+
+        Ok(match ty.0 {
+            0 => Self::Integer,
+            1 => Self::Hearts,
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid objective render type, {}", ty.0),
+            ))?,
+        })
+    }
+}
+
+impl Encodable for ObjectiveRenderType {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        VarInt(*self as i32).encode(writer)
+    }
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct ObjectiveInfo {
+    #[versioned]
+    pub display_name: TextComponent,
+    pub render_type: ObjectiveRenderType,
+    // the format of the objective's scores that don't have their own.
+    #[since(V1_20_3)]
+    #[versioned]
+    pub number_format: Option<Option<NumberFormat>>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ObjectiveAction {
+    Create(ObjectiveInfo),
+    Remove,
+    Update(ObjectiveInfo),
+}
+
+impl VersionedDecodable for ObjectiveAction {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        let mode = i8::decode(reader)?;
+
+        Ok(match mode {
+            0 => Self::Create(ObjectiveInfo::decode_versioned(reader, version)?),
+            1 => Self::Remove,
+            2 => Self::Update(ObjectiveInfo::decode_versioned(reader, version)?),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid objective mode, {}", mode),
+            ))?,
+        })
+    }
+}
+
+impl VersionedEncodable for ObjectiveAction {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        match self {
+            Self::Create(info) => {
+                0i8.encode(writer)?;
+                info.encode_versioned(writer, version)
+            }
+            Self::Remove => 1i8.encode(writer),
+            Self::Update(info) => {
+                2i8.encode(writer)?;
+                info.encode_versioned(writer, version)
+            }
+        }
+    }
+}
+
+// where an objective is shown. the team sidebars are only shown to the members of a team with that color.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum DisplaySlot {
+    List,
+    Sidebar,
+    BelowName,
+    TeamSidebar(u8),
+}
+
+impl DisplaySlot {
+    fn from_id(id: i32) -> Result<Self, std::io::Error> {
+        Ok(match id {
+            0 => Self::List,
+            1 => Self::Sidebar,
+            2 => Self::BelowName,
+            3..=18 => Self::TeamSidebar(id as u8 - 3),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid display slot, {}", id),
+            ))?,
+        })
+    }
+
+    pub fn id(&self) -> i32 {
+        match self {
+            Self::List => 0,
+            Self::Sidebar => 1,
+            Self::BelowName => 2,
+            Self::TeamSidebar(color) => *color as i32 + 3,
+        }
+    }
+}
+
+// a byte before 1.20.2 and a VarInt since.
+impl VersionedDecodable for DisplaySlot {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        if version < ProtocolVersion::V1_20_2 {
+            return Self::from_id(i8::decode(reader)? as i32);
+        }
+
+        Self::from_id(VarInt::decode(reader)?.0)
+    }
+}
+
+impl VersionedEncodable for DisplaySlot {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        if version < ProtocolVersion::V1_20_2 {
+            return (self.id() as i8).encode(writer);
+        }
+
+        VarInt(self.id()).encode(writer)
+    }
+}
+
+// the visibility is one of always, hideForOtherTeams, hideForOwnTeam or never and the collision rule one of always,
+// pushOtherTeams, pushOwnTeam or never.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct TeamInfo {
+    #[versioned]
+    pub display_name: TextComponent,
+    // 0x01 allows friendly fire and 0x02 shows invisible teammates.
+    pub friendly_flags: u8,
+    pub name_tag_visibility: String,
+    pub collision_rule: String,
+    pub color: VarInt,
+    #[versioned]
+    pub prefix: TextComponent,
+    #[versioned]
+    pub suffix: TextComponent,
+}
+
+// the entities are player names or the uuids of other entities.
+#[derive(Debug, PartialEq, Clone)]
+pub enum TeamAction {
+    Create {
+        info: TeamInfo,
+        entities: Vec<String>,
+    },
+    Remove,
+    UpdateInfo(TeamInfo),
+    AddEntities(Vec<String>),
+    RemoveEntities(Vec<String>),
+}
+
+impl VersionedDecodable for TeamAction {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        let mode = i8::decode(reader)?;
+
+        Ok(match mode {
+            0 => Self::Create {
+                info: TeamInfo::decode_versioned(reader, version)?,
+                entities: Vec::decode(reader)?,
+            },
+            1 => Self::Remove,
+            2 => Self::UpdateInfo(TeamInfo::decode_versioned(reader, version)?),
+            3 => Self::AddEntities(Vec::decode(reader)?),
+            4 => Self::RemoveEntities(Vec::decode(reader)?),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid team mode, {}", mode),
+            ))?,
+        })
+    }
+}
+
+impl VersionedEncodable for TeamAction {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        match self {
+            Self::Create { info, entities } => {
+                0i8.encode(writer)?;
+                info.encode_versioned(writer, version)?;
+                entities.encode(writer)
+            }
+            Self::Remove => 1i8.encode(writer),
+            Self::UpdateInfo(info) => {
+                2i8.encode(writer)?;
+                info.encode_versioned(writer, version)
+            }
+            Self::AddEntities(entities) => {
+                3i8.encode(writer)?;
+                entities.encode(writer)
+            }
+            Self::RemoveEntities(entities) => {
+                4i8.encode(writer)?;
+                entities.encode(writer)
+            }
+        }
+    }
+}
@@ -0,0 +1,1482 @@
+use std::io::{Read, Write};
+
+use proc_macros::MinecraftPacket;
+use types::packet::ProtocolVersion;
+
+use uuid::Uuid;
+
+use crate::{
+    identifier::{IdSet, Identifier, RegistryReference},
+    nbt::{self, OptionalValue},
+    packets::{Decodable, Encodable, VersionedDecodable, VersionedEncodable},
+    position::GlobalPosition,
+    profile::ProfileProperty,
+    sound::SoundReference,
+    text::TextComponent,
+    VarInt,
+};
+
+// the contents of an inventory slot, None when it's empty.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Slot(pub Option<ItemStack>);
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ItemStack {
+    pub item_id: VarInt,
+    pub count: i32,
+    pub data: ItemData,
+}
+
+// an item's extra data was an NBT compound before 1.20.5, since then it's a patch on the item's default components.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ItemData {
+    Nbt(OptionalValue),
+    Components {
+        added: Vec<ItemComponent>,
+        removed: Vec<VarInt>,
+    },
+}
+
+// every item component type in registry order, with the version it was added in and the one it was removed in. the id
+// of a component is its index among the components that exist in a version.
+const COMPONENTS: &[(&str, ProtocolVersion, Option<ProtocolVersion>)] = &[
+    ("minecraft:custom_data", ProtocolVersion::V1_20_5, None),
+    ("minecraft:max_stack_size", ProtocolVersion::V1_20_5, None),
+    ("minecraft:max_damage", ProtocolVersion::V1_20_5, None),
+    ("minecraft:damage", ProtocolVersion::V1_20_5, None),
+    ("minecraft:unbreakable", ProtocolVersion::V1_20_5, None),
+    ("minecraft:custom_name", ProtocolVersion::V1_20_5, None),
+    ("minecraft:item_name", ProtocolVersion::V1_20_5, None),
+    ("minecraft:lore", ProtocolVersion::V1_20_5, None),
+    ("minecraft:rarity", ProtocolVersion::V1_20_5, None),
+    ("minecraft:enchantments", ProtocolVersion::V1_20_5, None),
+    ("minecraft:can_place_on", ProtocolVersion::V1_20_5, None),
+    ("minecraft:can_break", ProtocolVersion::V1_20_5, None),
+    (
+        "minecraft:attribute_modifiers",
+        ProtocolVersion::V1_20_5,
+        None,
+    ),
+    (
+        "minecraft:custom_model_data",
+        ProtocolVersion::V1_20_5,
+        None,
+    ),
+    (
+        "minecraft:hide_additional_tooltip",
+        ProtocolVersion::V1_20_5,
+        None,
+    ),
+    ("minecraft:hide_tooltip", ProtocolVersion::V1_20_5, None),
+    ("minecraft:repair_cost", ProtocolVersion::V1_20_5, None),
+    (
+        "minecraft:creative_slot_lock",
+        ProtocolVersion::V1_20_5,
+        None,
+    ),
+    (
+        "minecraft:enchantment_glint_override",
+        ProtocolVersion::V1_20_5,
+        None,
+    ),
+    (
+        "minecraft:intangible_projectile",
+        ProtocolVersion::V1_20_5,
+        None,
+    ),
+    ("minecraft:food", ProtocolVersion::V1_20_5, None),
+    ("minecraft:fire_resistant", ProtocolVersion::V1_20_5, None),
+    ("minecraft:tool", ProtocolVersion::V1_20_5, None),
+    (
+        "minecraft:stored_enchantments",
+        ProtocolVersion::V1_20_5,
+        None,
+    ),
+    ("minecraft:dyed_color", ProtocolVersion::V1_20_5, None),
+    ("minecraft:map_color", ProtocolVersion::V1_20_5, None),
+    ("minecraft:map_id", ProtocolVersion::V1_20_5, None),
+    ("minecraft:map_decorations", ProtocolVersion::V1_20_5, None),
+    (
+        "minecraft:map_post_processing",
+        ProtocolVersion::V1_20_5,
+        None,
+    ),
+    (
+        "minecraft:charged_projectiles",
+        ProtocolVersion::V1_20_5,
+        None,
+    ),
+    ("minecraft:bundle_contents", ProtocolVersion::V1_20_5, None),
+    ("minecraft:potion_contents", ProtocolVersion::V1_20_5, None),
+    (
+        "minecraft:suspicious_stew_effects",
+        ProtocolVersion::V1_20_5,
+        None,
+    ),
+    (
+        "minecraft:writable_book_content",
+        ProtocolVersion::V1_20_5,
+        None,
+    ),
+    (
+        "minecraft:written_book_content",
+        ProtocolVersion::V1_20_5,
+        None,
+    ),
+    ("minecraft:trim", ProtocolVersion::V1_20_5, None),
+    (
+        "minecraft:debug_stick_state",
+        ProtocolVersion::V1_20_5,
+        None,
+    ),
+    ("minecraft:entity_data", ProtocolVersion::V1_20_5, None),
+    (
+        "minecraft:bucket_entity_data",
+        ProtocolVersion::V1_20_5,
+        None,
+    ),
+    (
+        "minecraft:block_entity_data",
+        ProtocolVersion::V1_20_5,
+        None,
+    ),
+    ("minecraft:instrument", ProtocolVersion::V1_20_5, None),
+    (
+        "minecraft:ominous_bottle_amplifier",
+        ProtocolVersion::V1_20_5,
+        None,
+    ),
+    ("minecraft:jukebox_playable", ProtocolVersion::V1_21, None),
+    ("minecraft:recipes", ProtocolVersion::V1_20_5, None),
+    (
+        "minecraft:lodestone_tracker",
+        ProtocolVersion::V1_20_5,
+        None,
+    ),
+    (
+        "minecraft:firework_explosion",
+        ProtocolVersion::V1_20_5,
+        None,
+    ),
+    ("minecraft:fireworks", ProtocolVersion::V1_20_5, None),
+    ("minecraft:profile", ProtocolVersion::V1_20_5, None),
+    ("minecraft:note_block_sound", ProtocolVersion::V1_20_5, None),
+    ("minecraft:banner_patterns", ProtocolVersion::V1_20_5, None),
+    ("minecraft:base_color", ProtocolVersion::V1_20_5, None),
+    ("minecraft:pot_decorations", ProtocolVersion::V1_20_5, None),
+    ("minecraft:container", ProtocolVersion::V1_20_5, None),
+    ("minecraft:block_state", ProtocolVersion::V1_20_5, None),
+    ("minecraft:bees", ProtocolVersion::V1_20_5, None),
+    ("minecraft:lock", ProtocolVersion::V1_20_5, None),
+    ("minecraft:container_loot", ProtocolVersion::V1_20_5, None),
+];
+
+// 1.21.2 reordered the registry, only the first components kept their ids. the others are rejected there instead of
+// being decoded as the wrong component.
+const KNOWN_SINCE_1_21_2: usize = 7;
+
+// items can contain items, e.g. in a bundle or a shulker box, and effects can hide effects. both are limited like
+// NBT's nesting, but far lower since a nested item takes a lot more stack than a tag.
+const MAX_DEPTH: usize = 64;
+
+fn components(version: ProtocolVersion) -> impl Iterator<Item = &'static str> {
+    let known = if version >= ProtocolVersion::V1_21_2 {
+        KNOWN_SINCE_1_21_2
+    } else {
+        COMPONENTS.len()
+    };
+
+    COMPONENTS
+        .iter()
+        .filter(move |(_, since, until)| {
+            version >= *since && until.is_none_or(|until| version < until)
+        })
+        .take(known)
+        .map(|(name, _, _)| *name)
+}
+
+pub fn component_name(id: i32, version: ProtocolVersion) -> Option<&'static str> {
+    components(version).nth(id.try_into().ok()?)
+}
+
+pub fn component_id(name: &str, version: ProtocolVersion) -> Option<i32> {
+    components(version)
+        .position(|component| component == name)
+        .map(|id| id as i32)
+}
+
+fn decode_slots<R: Read>(
+    reader: &mut R,
+    version: ProtocolVersion,
+    depth: usize,
+) -> Result<Vec<Slot>, std::io::Error> {
+    let len = VarInt::decode(reader)?.0;
+
+    (0..len)
+        .map(|_| Slot::decode_nested(reader, version, depth))
+        .collect()
+}
+
+// a component of an item stack, the payload of each type has its own layout and isn't prefixed with its length.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ItemComponent {
+    CustomData(nbt::Value),
+    MaxStackSize(VarInt),
+    MaxDamage(VarInt),
+    Damage(VarInt),
+    Unbreakable {
+        show_in_tooltip: bool,
+    },
+    CustomName(TextComponent),
+    ItemName(TextComponent),
+    Lore(Vec<TextComponent>),
+    Rarity(Rarity),
+    Enchantments(Enchantments),
+    CanPlaceOn(AdventureModePredicate),
+    CanBreak(AdventureModePredicate),
+    AttributeModifiers(AttributeModifiers),
+    CustomModelData(VarInt),
+    HideAdditionalTooltip,
+    HideTooltip,
+    RepairCost(VarInt),
+    CreativeSlotLock,
+    EnchantmentGlintOverride(bool),
+    // always an empty compound.
+    IntangibleProjectile,
+    Food(Box<Food>),
+    FireResistant,
+    Tool(Tool),
+    StoredEnchantments(Enchantments),
+    DyedColor {
+        color: i32,
+        show_in_tooltip: bool,
+    },
+    MapColor(i32),
+    MapId(VarInt),
+    MapDecorations(nbt::Value),
+    MapPostProcessing(VarInt),
+    ChargedProjectiles(Vec<Slot>),
+    BundleContents(Vec<Slot>),
+    PotionContents(PotionContents),
+    SuspiciousStewEffects(Vec<SuspiciousStewEffect>),
+    WritableBookContent(Vec<FilteredString>),
+    WrittenBookContent(Box<WrittenBookContent>),
+    Trim(Box<ArmorTrim>),
+    DebugStickState(nbt::Value),
+    EntityData(nbt::Value),
+    BucketEntityData(nbt::Value),
+    BlockEntityData(nbt::Value),
+    Instrument(RegistryReference<Instrument>),
+    OminousBottleAmplifier(VarInt),
+    JukeboxPlayable(Box<JukeboxPlayable>),
+    Recipes(nbt::Value),
+    LodestoneTracker {
+        target: Option<GlobalPosition>,
+        tracked: bool,
+    },
+    FireworkExplosion(FireworkExplosion),
+    Fireworks {
+        flight_duration: VarInt,
+        explosions: Vec<FireworkExplosion>,
+    },
+    Profile(ResolvableProfile),
+    NoteBlockSound(Identifier),
+    BannerPatterns(Vec<BannerPatternLayer>),
+    BaseColor(VarInt),
+    PotDecorations(Vec<VarInt>),
+    Container(Vec<Slot>),
+    BlockState(Vec<BlockStateProperty>),
+    Bees(Vec<Bee>),
+    Lock(nbt::Value),
+    ContainerLoot(nbt::Value),
+}
+
+impl ItemComponent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::CustomData(_) => "minecraft:custom_data",
+            Self::MaxStackSize(_) => "minecraft:max_stack_size",
+            Self::MaxDamage(_) => "minecraft:max_damage",
+            Self::Damage(_) => "minecraft:damage",
+            Self::Unbreakable { .. } => "minecraft:unbreakable",
+            Self::CustomName(_) => "minecraft:custom_name",
+            Self::ItemName(_) => "minecraft:item_name",
+            Self::Lore(_) => "minecraft:lore",
+            Self::Rarity(_) => "minecraft:rarity",
+            Self::Enchantments(_) => "minecraft:enchantments",
+            Self::CanPlaceOn(_) => "minecraft:can_place_on",
+            Self::CanBreak(_) => "minecraft:can_break",
+            Self::AttributeModifiers(_) => "minecraft:attribute_modifiers",
+            Self::CustomModelData(_) => "minecraft:custom_model_data",
+            Self::HideAdditionalTooltip => "minecraft:hide_additional_tooltip",
+            Self::HideTooltip => "minecraft:hide_tooltip",
+            Self::RepairCost(_) => "minecraft:repair_cost",
+            Self::CreativeSlotLock => "minecraft:creative_slot_lock",
+            Self::EnchantmentGlintOverride(_) => "minecraft:enchantment_glint_override",
+            Self::IntangibleProjectile => "minecraft:intangible_projectile",
+            Self::Food(_) => "minecraft:food",
+            Self::FireResistant => "minecraft:fire_resistant",
+            Self::Tool(_) => "minecraft:tool",
+            Self::StoredEnchantments(_) => "minecraft:stored_enchantments",
+            Self::DyedColor { .. } => "minecraft:dyed_color",
+            Self::MapColor(_) => "minecraft:map_color",
+            Self::MapId(_) => "minecraft:map_id",
+            Self::MapDecorations(_) => "minecraft:map_decorations",
+            Self::MapPostProcessing(_) => "minecraft:map_post_processing",
+            Self::ChargedProjectiles(_) => "minecraft:charged_projectiles",
+            Self::BundleContents(_) => "minecraft:bundle_contents",
+            Self::PotionContents(_) => "minecraft:potion_contents",
+            Self::SuspiciousStewEffects(_) => "minecraft:suspicious_stew_effects",
+            Self::WritableBookContent(_) => "minecraft:writable_book_content",
+            Self::WrittenBookContent(_) => "minecraft:written_book_content",
+            Self::Trim(_) => "minecraft:trim",
+            Self::DebugStickState(_) => "minecraft:debug_stick_state",
+            Self::EntityData(_) => "minecraft:entity_data",
+            Self::BucketEntityData(_) => "minecraft:bucket_entity_data",
+            Self::BlockEntityData(_) => "minecraft:block_entity_data",
+            Self::Instrument(_) => "minecraft:instrument",
+            Self::OminousBottleAmplifier(_) => "minecraft:ominous_bottle_amplifier",
+            Self::JukeboxPlayable(_) => "minecraft:jukebox_playable",
+            Self::Recipes(_) => "minecraft:recipes",
+            Self::LodestoneTracker { .. } => "minecraft:lodestone_tracker",
+            Self::FireworkExplosion(_) => "minecraft:firework_explosion",
+            Self::Fireworks { .. } => "minecraft:fireworks",
+            Self::Profile(_) => "minecraft:profile",
+            Self::NoteBlockSound(_) => "minecraft:note_block_sound",
+            Self::BannerPatterns(_) => "minecraft:banner_patterns",
+            Self::BaseColor(_) => "minecraft:base_color",
+            Self::PotDecorations(_) => "minecraft:pot_decorations",
+            Self::Container(_) => "minecraft:container",
+            Self::BlockState(_) => "minecraft:block_state",
+            Self::Bees(_) => "minecraft:bees",
+            Self::Lock(_) => "minecraft:lock",
+            Self::ContainerLoot(_) => "minecraft:container_loot",
+        }
+    }
+
+    pub fn id(&self, version: ProtocolVersion) -> Result<VarInt, std::io::Error> {
+        let name = self.name();
+
+        let id = component_id(name, version).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                format!(
+                    "Item component {} isn't known in protocol version {}",
+                    name, version.0
+                ),
+            )
+        })?;
+
+        Ok(VarInt(id))
+    }
+
+    fn decode_nested<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+        depth: usize,
+    ) -> Result<Self, std::io::Error> {
+        let id = VarInt::decode(reader)?;
+
+        let name = component_name(id.0, version).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "Unknown item component {} in protocol version {}",
+                    id.0, version.0
+                ),
+            )
+        })?;
+
+        Ok(match name {
+            "minecraft:custom_data" => {
+                Self::CustomData(nbt::Value::decode_versioned(reader, version)?)
+            }
+            "minecraft:max_stack_size" => Self::MaxStackSize(VarInt::decode(reader)?),
+            "minecraft:max_damage" => Self::MaxDamage(VarInt::decode(reader)?),
+            "minecraft:damage" => Self::Damage(VarInt::decode(reader)?),
+            "minecraft:unbreakable" => Self::Unbreakable {
+                show_in_tooltip: bool::decode(reader)?,
+            },
+            "minecraft:custom_name" => {
+                Self::CustomName(TextComponent::decode_versioned(reader, version)?)
+            }
+            "minecraft:item_name" => {
+                Self::ItemName(TextComponent::decode_versioned(reader, version)?)
+            }
+            "minecraft:lore" => Self::Lore(Vec::decode_versioned(reader, version)?),
+            "minecraft:rarity" => Self::Rarity(Rarity::decode(reader)?),
+            "minecraft:enchantments" => Self::Enchantments(Enchantments::decode(reader)?),
+            "minecraft:can_place_on" => {
+                Self::CanPlaceOn(AdventureModePredicate::decode_versioned(reader, version)?)
+            }
+            "minecraft:can_break" => {
+                Self::CanBreak(AdventureModePredicate::decode_versioned(reader, version)?)
+            }
+            "minecraft:attribute_modifiers" => {
+                Self::AttributeModifiers(AttributeModifiers::decode_versioned(reader, version)?)
+            }
+            "minecraft:custom_model_data" => Self::CustomModelData(VarInt::decode(reader)?),
+            "minecraft:hide_additional_tooltip" => Self::HideAdditionalTooltip,
+            "minecraft:hide_tooltip" => Self::HideTooltip,
+            "minecraft:repair_cost" => Self::RepairCost(VarInt::decode(reader)?),
+            "minecraft:creative_slot_lock" => Self::CreativeSlotLock,
+            "minecraft:enchantment_glint_override" => {
+                Self::EnchantmentGlintOverride(bool::decode(reader)?)
+            }
+            "minecraft:intangible_projectile" => {
+                nbt::Value::decode_versioned(reader, version)?;
+                Self::IntangibleProjectile
+            }
+            "minecraft:food" => Self::Food(Box::new(Food::decode_nested(reader, version, depth)?)),
+            "minecraft:fire_resistant" => Self::FireResistant,
+            "minecraft:tool" => Self::Tool(Tool::decode(reader)?),
+            "minecraft:stored_enchantments" => {
+                Self::StoredEnchantments(Enchantments::decode(reader)?)
+            }
+            "minecraft:dyed_color" => Self::DyedColor {
+                color: i32::decode(reader)?,
+                show_in_tooltip: bool::decode(reader)?,
+            },
+            "minecraft:map_color" => Self::MapColor(i32::decode(reader)?),
+            "minecraft:map_id" => Self::MapId(VarInt::decode(reader)?),
+            "minecraft:map_decorations" => {
+                Self::MapDecorations(nbt::Value::decode_versioned(reader, version)?)
+            }
+            "minecraft:map_post_processing" => Self::MapPostProcessing(VarInt::decode(reader)?),
+            "minecraft:charged_projectiles" => {
+                Self::ChargedProjectiles(decode_slots(reader, version, depth)?)
+            }
+            "minecraft:bundle_contents" => {
+                Self::BundleContents(decode_slots(reader, version, depth)?)
+            }
+            "minecraft:potion_contents" => Self::PotionContents(PotionContents::decode(reader)?),
+            "minecraft:suspicious_stew_effects" => {
+                Self::SuspiciousStewEffects(Vec::decode(reader)?)
+            }
+            "minecraft:writable_book_content" => Self::WritableBookContent(Vec::decode(reader)?),
+            "minecraft:written_book_content" => Self::WrittenBookContent(Box::new(
+                WrittenBookContent::decode_versioned(reader, version)?,
+            )),
+            "minecraft:trim" => Self::Trim(Box::new(ArmorTrim::decode_versioned(reader, version)?)),
+            "minecraft:debug_stick_state" => {
+                Self::DebugStickState(nbt::Value::decode_versioned(reader, version)?)
+            }
+            "minecraft:entity_data" => {
+                Self::EntityData(nbt::Value::decode_versioned(reader, version)?)
+            }
+            "minecraft:bucket_entity_data" => {
+                Self::BucketEntityData(nbt::Value::decode_versioned(reader, version)?)
+            }
+            "minecraft:block_entity_data" => {
+                Self::BlockEntityData(nbt::Value::decode_versioned(reader, version)?)
+            }
+            "minecraft:instrument" => Self::Instrument(RegistryReference::decode(reader)?),
+            "minecraft:ominous_bottle_amplifier" => {
+                Self::OminousBottleAmplifier(VarInt::decode(reader)?)
+            }
+            "minecraft:jukebox_playable" => Self::JukeboxPlayable(Box::new(
+                JukeboxPlayable::decode_versioned(reader, version)?,
+            )),
+            "minecraft:recipes" => Self::Recipes(nbt::Value::decode_versioned(reader, version)?),
+            "minecraft:lodestone_tracker" => Self::LodestoneTracker {
+                target: Option::decode(reader)?,
+                tracked: bool::decode(reader)?,
+            },
+            "minecraft:firework_explosion" => {
+                Self::FireworkExplosion(FireworkExplosion::decode(reader)?)
+            }
+            "minecraft:fireworks" => Self::Fireworks {
+                flight_duration: VarInt::decode(reader)?,
+                explosions: Vec::decode(reader)?,
+            },
+            "minecraft:profile" => Self::Profile(ResolvableProfile::decode(reader)?),
+            "minecraft:note_block_sound" => Self::NoteBlockSound(Identifier::decode(reader)?),
+            "minecraft:banner_patterns" => Self::BannerPatterns(Vec::decode(reader)?),
+            "minecraft:base_color" => Self::BaseColor(VarInt::decode(reader)?),
+            "minecraft:pot_decorations" => Self::PotDecorations(Vec::decode(reader)?),
+            "minecraft:container" => Self::Container(decode_slots(reader, version, depth)?),
+            "minecraft:block_state" => Self::BlockState(Vec::decode(reader)?),
+            "minecraft:bees" => Self::Bees(Vec::decode_versioned(reader, version)?),
+            "minecraft:lock" => Self::Lock(nbt::Value::decode_versioned(reader, version)?),
+            "minecraft:container_loot" => {
+                Self::ContainerLoot(nbt::Value::decode_versioned(reader, version)?)
+            }
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Item component {} can't be decoded.", name),
+            ))?,
+        })
+    }
+}
+
+impl VersionedDecodable for ItemComponent {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        Self::decode_nested(reader, version, 0)
+    }
+}
+
+impl VersionedEncodable for ItemComponent {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        self.id(version)?.encode(writer)?;
+
+        match self {
+            Self::CustomData(data)
+            | Self::MapDecorations(data)
+            | Self::DebugStickState(data)
+            | Self::EntityData(data)
+            | Self::BucketEntityData(data)
+            | Self::BlockEntityData(data)
+            | Self::Recipes(data)
+            | Self::Lock(data)
+            | Self::ContainerLoot(data) => data.encode_versioned(writer, version),
+            Self::MaxStackSize(value)
+            | Self::MaxDamage(value)
+            | Self::Damage(value)
+            | Self::CustomModelData(value)
+            | Self::RepairCost(value)
+            | Self::MapId(value)
+            | Self::MapPostProcessing(value)
+            | Self::OminousBottleAmplifier(value)
+            | Self::BaseColor(value) => value.encode(writer),
+            Self::Unbreakable { show_in_tooltip } => show_in_tooltip.encode(writer),
+            Self::CustomName(name) | Self::ItemName(name) => name.encode_versioned(writer, version),
+            Self::Lore(lines) => lines.encode_versioned(writer, version),
+            Self::Rarity(rarity) => rarity.encode(writer),
+            Self::Enchantments(enchantments) | Self::StoredEnchantments(enchantments) => {
+                enchantments.encode(writer)
+            }
+            Self::CanPlaceOn(predicate) | Self::CanBreak(predicate) => {
+                predicate.encode_versioned(writer, version)
+            }
+            Self::AttributeModifiers(modifiers) => modifiers.encode_versioned(writer, version),
+            Self::HideAdditionalTooltip
+            | Self::HideTooltip
+            | Self::CreativeSlotLock
+            | Self::FireResistant => Ok(()),
+            Self::EnchantmentGlintOverride(value) => value.encode(writer),
+            Self::IntangibleProjectile => {
+                nbt::Value::Compound(Default::default()).encode_versioned(writer, version)
+            }
+            Self::Food(food) => food.encode_versioned(writer, version),
+            Self::Tool(tool) => tool.encode(writer),
+            Self::DyedColor {
+                color,
+                show_in_tooltip,
+            } => {
+                color.encode(writer)?;
+                show_in_tooltip.encode(writer)
+            }
+            Self::MapColor(color) => color.encode(writer),
+            Self::ChargedProjectiles(items)
+            | Self::BundleContents(items)
+            | Self::Container(items) => items.encode_versioned(writer, version),
+            Self::PotionContents(contents) => contents.encode(writer),
+            Self::SuspiciousStewEffects(effects) => effects.encode(writer),
+            Self::WritableBookContent(pages) => pages.encode(writer),
+            Self::WrittenBookContent(content) => content.encode_versioned(writer, version),
+            Self::Trim(trim) => trim.encode_versioned(writer, version),
+            Self::Instrument(instrument) => instrument.encode(writer),
+            Self::JukeboxPlayable(playable) => playable.encode_versioned(writer, version),
+            Self::LodestoneTracker { target, tracked } => {
+                target.encode(writer)?;
+                tracked.encode(writer)
+            }
+            Self::FireworkExplosion(explosion) => explosion.encode(writer),
+            Self::Fireworks {
+                flight_duration,
+                explosions,
+            } => {
+                flight_duration.encode(writer)?;
+                explosions.encode(writer)
+            }
+            Self::Profile(profile) => profile.encode(writer),
+            Self::NoteBlockSound(sound) => sound.encode(writer),
+            Self::BannerPatterns(layers) => layers.encode(writer),
+            Self::PotDecorations(decorations) => decorations.encode(writer),
+            Self::BlockState(properties) => properties.encode(writer),
+            Self::Bees(bees) => bees.encode_versioned(writer, version),
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Rarity {
+    Common = 0,
+    Uncommon = 1,
+    Rare = 2,
+    Epic = 3,
+}
+
+impl Decodable for Rarity {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let rarity = VarInt::decode(reader)?;
+
+        Ok(match rarity.0 {
+            0 => Self::Common,
+            1 => Self::Uncommon,
+            2 => Self::Rare,
+            3 => Self::Epic,
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid rarity, {}", rarity.0),
+            ))?,
+        })
+    }
+}
+
+impl Encodable for Rarity {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        VarInt(*self as i32).encode(writer)
+    }
+}
+
+// the enchantments of an item or those stored in an enchanted book, by their id in the enchantment registry.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct Enchantments {
+    pub enchantments: Vec<Enchantment>,
+    pub show_in_tooltip: bool,
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct Enchantment {
+    pub id: VarInt,
+    pub level: VarInt,
+}
+
+// the blocks an item can be placed on or break in adventure mode.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct AdventureModePredicate {
+    #[versioned]
+    pub predicates: Vec<BlockPredicate>,
+    pub show_in_tooltip: bool,
+}
+
+// every part that's sent has to match.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct BlockPredicate {
+    pub blocks: Option<IdSet>,
+    pub properties: Option<Vec<PropertyMatcher>>,
+    #[versioned]
+    pub nbt: Option<nbt::Value>,
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct PropertyMatcher {
+    pub name: String,
+    pub value: PropertyValue,
+}
+
+// an exact value is sent after true, a range after false.
+#[derive(Debug, PartialEq, Clone)]
+pub enum PropertyValue {
+    Exact(String),
+    Range {
+        min: Option<String>,
+        max: Option<String>,
+    },
+}
+
+impl Decodable for PropertyValue {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        if bool::decode(reader)? {
+            return Ok(Self::Exact(String::decode(reader)?));
+        }
+
+        Ok(Self::Range {
+            min: Option::decode(reader)?,
+            max: Option::decode(reader)?,
+        })
+    }
+}
+
+impl Encodable for PropertyValue {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        match self {
+            Self::Exact(value) => {
+                true.encode(writer)?;
+                value.encode(writer)
+            }
+            Self::Range { min, max } => {
+                false.encode(writer)?;
+                min.encode(writer)?;
+                max.encode(writer)
+            }
+        }
+    }
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct AttributeModifiers {
+    #[versioned]
+    pub modifiers: Vec<AttributeModifier>,
+    pub show_in_tooltip: bool,
+}
+
+// modifiers were identified by a uuid and a name until 1.21, since then by an identifier.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct AttributeModifier {
+    pub attribute: VarInt,
+    #[until(V1_21)]
+    pub uuid: Option<Uuid>,
+    #[until(V1_21)]
+    pub name: Option<String>,
+    #[since(V1_21)]
+    pub id: Option<Identifier>,
+    pub amount: f64,
+    pub operation: VarInt,
+    pub slot: VarInt,
+}
+
+// the item the food turns into after eating it is only sent since 1.21.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Food {
+    pub nutrition: VarInt,
+    pub saturation: f32,
+    pub can_always_eat: bool,
+    pub eat_seconds: f32,
+    pub using_converts_to: Option<Slot>,
+    pub effects: Vec<PossibleEffect>,
+}
+
+impl Food {
+    fn decode_nested<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+        depth: usize,
+    ) -> Result<Self, std::io::Error> {
+        let nutrition = VarInt::decode(reader)?;
+        let saturation = f32::decode(reader)?;
+        let can_always_eat = bool::decode(reader)?;
+        let eat_seconds = f32::decode(reader)?;
+
+        let using_converts_to = if version >= ProtocolVersion::V1_21 && bool::decode(reader)? {
+            Some(Slot::decode_nested(reader, version, depth)?)
+        } else {
+            None
+        };
+
+        Ok(Food {
+            nutrition,
+            saturation,
+            can_always_eat,
+            eat_seconds,
+            using_converts_to,
+            effects: Vec::decode(reader)?,
+        })
+    }
+}
+
+impl VersionedDecodable for Food {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        Self::decode_nested(reader, version, 0)
+    }
+}
+
+impl VersionedEncodable for Food {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        self.nutrition.encode(writer)?;
+        self.saturation.encode(writer)?;
+        self.can_always_eat.encode(writer)?;
+        self.eat_seconds.encode(writer)?;
+
+        if version >= ProtocolVersion::V1_21 {
+            self.using_converts_to.encode_versioned(writer, version)?;
+        }
+
+        self.effects.encode(writer)
+    }
+}
+
+// an effect applied with the given probability when the food is eaten.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct PossibleEffect {
+    pub effect: EffectInstance,
+    pub probability: f32,
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct EffectInstance {
+    pub effect: VarInt,
+    pub details: EffectDetails,
+}
+
+// a weaker effect of the same type can be hidden behind a stronger one, it takes over when the stronger one runs out.
+#[derive(Debug, PartialEq, Clone)]
+pub struct EffectDetails {
+    pub amplifier: VarInt,
+    pub duration: VarInt,
+    pub ambient: bool,
+    pub show_particles: bool,
+    pub show_icon: bool,
+    pub hidden_effect: Option<Box<EffectDetails>>,
+}
+
+impl EffectDetails {
+    fn decode_nested<R: Read>(reader: &mut R, depth: usize) -> Result<Self, std::io::Error> {
+        if depth > MAX_DEPTH {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Hidden effects are nested deeper than {}", MAX_DEPTH),
+            ));
+        }
+
+        let amplifier = VarInt::decode(reader)?;
+        let duration = VarInt::decode(reader)?;
+        let ambient = bool::decode(reader)?;
+        let show_particles = bool::decode(reader)?;
+        let show_icon = bool::decode(reader)?;
+
+        let hidden_effect = if bool::decode(reader)? {
+            Some(Box::new(Self::decode_nested(reader, depth + 1)?))
+        } else {
+            None
+        };
+
+        Ok(EffectDetails {
+            amplifier,
+            duration,
+            ambient,
+            show_particles,
+            show_icon,
+            hidden_effect,
+        })
+    }
+}
+
+impl Decodable for EffectDetails {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        Self::decode_nested(reader, 0)
+    }
+}
+
+impl Encodable for EffectDetails {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        self.amplifier.encode(writer)?;
+        self.duration.encode(writer)?;
+        self.ambient.encode(writer)?;
+        self.show_particles.encode(writer)?;
+        self.show_icon.encode(writer)?;
+
+        match &self.hidden_effect {
+            Some(hidden_effect) => {
+                true.encode(writer)?;
+                hidden_effect.encode(writer)
+            }
+            None => false.encode(writer),
+        }
+    }
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct Tool {
+    pub rules: Vec<ToolRule>,
+    pub default_mining_speed: f32,
+    pub damage_per_block: VarInt,
+}
+
+// the first rule that matches a block is used, a rule without a speed or drop flag keeps the default.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct ToolRule {
+    pub blocks: IdSet,
+    pub speed: Option<f32>,
+    pub correct_for_drops: Option<bool>,
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct PotionContents {
+    pub potion: Option<VarInt>,
+    pub custom_color: Option<i32>,
+    pub custom_effects: Vec<EffectInstance>,
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct SuspiciousStewEffect {
+    pub effect: VarInt,
+    pub duration: VarInt,
+}
+
+// book text with the version shown to players with chat filtering enabled, if it differs.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct FilteredString {
+    pub raw: String,
+    pub filtered: Option<String>,
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct FilteredText {
+    #[versioned]
+    pub raw: TextComponent,
+    #[versioned]
+    pub filtered: Option<TextComponent>,
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct WrittenBookContent {
+    pub title: FilteredString,
+    pub author: String,
+    pub generation: VarInt,
+    #[versioned]
+    pub pages: Vec<FilteredText>,
+    pub resolved: bool,
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct ArmorTrim {
+    #[versioned]
+    pub material: RegistryReference<TrimMaterial>,
+    #[versioned]
+    pub pattern: RegistryReference<TrimPattern>,
+    pub show_in_tooltip: bool,
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct TrimMaterial {
+    pub asset_name: String,
+    pub ingredient: VarInt,
+    pub item_model_index: f32,
+    pub override_armor_materials: Vec<TrimMaterialOverride>,
+    #[versioned]
+    pub description: TextComponent,
+}
+
+// the asset used instead of the material's own on armor of the given material, e.g. darker iron on iron armor.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct TrimMaterialOverride {
+    pub armor_material: VarInt,
+    pub asset_name: String,
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct TrimPattern {
+    pub asset_id: Identifier,
+    pub template_item: VarInt,
+    #[versioned]
+    pub description: TextComponent,
+    pub decal: bool,
+}
+
+// a goat horn's sound, the duration is in ticks.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct Instrument {
+    pub sound_event: SoundReference,
+    pub use_duration: VarInt,
+    pub range: f32,
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct JukeboxPlayable {
+    #[versioned]
+    pub song: JukeboxSongReference,
+    pub show_in_tooltip: bool,
+}
+
+// a song the client knows is sent as a registry entry after true, otherwise by its name after false.
+#[derive(Debug, PartialEq, Clone)]
+pub enum JukeboxSongReference {
+    Entry(Box<RegistryReference<JukeboxSong>>),
+    Name(Identifier),
+}
+
+impl VersionedDecodable for JukeboxSongReference {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        if bool::decode(reader)? {
+            return Ok(Self::Entry(Box::new(RegistryReference::decode_versioned(
+                reader, version,
+            )?)));
+        }
+
+        Ok(Self::Name(Identifier::decode(reader)?))
+    }
+}
+
+impl VersionedEncodable for JukeboxSongReference {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        match self {
+            Self::Entry(song) => {
+                true.encode(writer)?;
+                song.encode_versioned(writer, version)
+            }
+            Self::Name(name) => {
+                false.encode(writer)?;
+                name.encode(writer)
+            }
+        }
+    }
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct JukeboxSong {
+    pub sound_event: SoundReference,
+    #[versioned]
+    pub description: TextComponent,
+    pub length_in_seconds: f32,
+    pub comparator_output: VarInt,
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct FireworkExplosion {
+    pub shape: VarInt,
+    pub colors: Vec<i32>,
+    pub fade_colors: Vec<i32>,
+    pub has_trail: bool,
+    pub has_twinkle: bool,
+}
+
+// a player head's profile, the client resolves the missing parts itself.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct ResolvableProfile {
+    pub name: Option<String>,
+    pub uuid: Option<Uuid>,
+    pub properties: Vec<ProfileProperty>,
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct BannerPatternLayer {
+    pub pattern: RegistryReference<BannerPattern>,
+    pub color: VarInt,
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct BannerPattern {
+    pub asset_id: Identifier,
+    pub translation_key: String,
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct BlockStateProperty {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct Bee {
+    #[versioned]
+    pub entity_data: nbt::Value,
+    pub ticks_in_hive: VarInt,
+    pub min_ticks_in_hive: VarInt,
+}
+
+impl Slot {
+    fn decode_nested<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+        depth: usize,
+    ) -> Result<Self, std::io::Error> {
+        if depth > MAX_DEPTH {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Items are nested deeper than {}", MAX_DEPTH),
+            ));
+        }
+
+        if version < ProtocolVersion::V1_20_5 {
+            if !bool::decode(reader)? {
+                return Ok(Slot(None));
+            }
+
+            return Ok(Slot(Some(ItemStack {
+                item_id: VarInt::decode(reader)?,
+                count: i8::decode(reader)? as i32,
+                data: ItemData::Nbt(OptionalValue::decode_versioned(reader, version)?),
+            })));
+        }
+
+        // an empty slot is just a count of 0.
+        let count = VarInt::decode(reader)?.0;
+        if count <= 0 {
+            return Ok(Slot(None));
+        }
+
+        let item_id = VarInt::decode(reader)?;
+        let added_len = VarInt::decode(reader)?.0;
+        let removed_len = VarInt::decode(reader)?.0;
+
+        let added = (0..added_len)
+            .map(|_| ItemComponent::decode_nested(reader, version, depth + 1))
+            .collect::<Result<_, _>>()?;
+        let removed = (0..removed_len)
+            .map(|_| VarInt::decode(reader))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Slot(Some(ItemStack {
+            item_id,
+            count,
+            data: ItemData::Components { added, removed },
+        })))
+    }
+}
+
+impl VersionedDecodable for Slot {
+    fn decode_versioned<R: Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        Self::decode_nested(reader, version, 0)
+    }
+}
+
+impl VersionedEncodable for Slot {
+    fn encode_versioned<W: Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        let Some(stack) = &self.0 else {
+            if version < ProtocolVersion::V1_20_5 {
+                return false.encode(writer);
+            }
+
+            return VarInt(0).encode(writer);
+        };
+
+        match (&stack.data, version < ProtocolVersion::V1_20_5) {
+            (ItemData::Nbt(data), true) => {
+                true.encode(writer)?;
+                stack.item_id.encode(writer)?;
+                let count = i8::try_from(stack.count).map_err(|_| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        format!(
+                            "Item count {} doesn't fit in a byte before 1.20.5.",
+                            stack.count
+                        ),
+                    )
+                })?;
+
+                count.encode(writer)?;
+                data.encode_versioned(writer, version)
+            }
+            (ItemData::Components { added, removed }, false) => {
+                VarInt(stack.count).encode(writer)?;
+                stack.item_id.encode(writer)?;
+                VarInt(added.len() as i32).encode(writer)?;
+                VarInt(removed.len() as i32).encode(writer)?;
+
+                for component in added {
+                    component.encode_versioned(writer, version)?;
+                }
+
+                for id in removed {
+                    id.encode(writer)?;
+                }
+
+                Ok(())
+            }
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Item data is NBT before 1.20.5 and components since.",
+            )),
+        }
+    }
+}
+
+// a slot the client changed with a click, by its index in the window.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct ChangedSlot {
+    pub slot: i16,
+    #[versioned]
+    pub item: Slot,
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClickMode {
+    Pickup = 0,
+    QuickMove = 1,
+    Swap = 2,
+    Clone = 3,
+    Throw = 4,
+    QuickCraft = 5,
+    PickupAll = 6,
+}
+
+impl Decodable for ClickMode {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let mode = VarInt::decode(reader)?;
+
+        Ok(match mode.0 {
+            0 => Self::Pickup,
+            1 => Self::QuickMove,
+            2 => Self::Swap,
+            3 => Self::Clone,
+            4 => Self::Throw,
+            5 => Self::QuickCraft,
+            6 => Self::PickupAll,
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid click mode, {}", mode.0),
+            ))?,
+        })
+    }
+}
+
+impl Encodable for ClickMode {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        VarInt(*self as i32).encode(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{nbt, position::Position};
+
+    use super::*;
+
+    fn nbt_stack() -> Slot {
+        Slot(Some(ItemStack {
+            item_id: VarInt(5),
+            count: 3,
+            data: ItemData::Nbt(OptionalValue(Some(nbt!({ "Damage": 4i32 })))),
+        }))
+    }
+
+    fn component_stack(added: Vec<ItemComponent>) -> Slot {
+        Slot(Some(ItemStack {
+            item_id: VarInt(5),
+            count: 70,
+            data: ItemData::Components {
+                added,
+                removed: vec![VarInt(9)],
+            },
+        }))
+    }
+
+    fn round_trip<T>(value: &T, version: ProtocolVersion) -> T
+    where
+        T: VersionedEncodable + VersionedDecodable,
+    {
+        let mut buf = Vec::new();
+        value.encode_versioned(&mut buf, version).unwrap();
+
+        let mut reader = &buf[..];
+        let decoded = T::decode_versioned(&mut reader, version).unwrap();
+        assert!(reader.is_empty());
+
+        decoded
+    }
+
+    fn food() -> Food {
+        let details = |amplifier, hidden_effect| EffectDetails {
+            amplifier: VarInt(amplifier),
+            duration: VarInt(200),
+            ambient: false,
+            show_particles: true,
+            show_icon: true,
+            hidden_effect,
+        };
+
+        Food {
+            nutrition: VarInt(4),
+            saturation: 2.5,
+            can_always_eat: false,
+            eat_seconds: 1.6,
+            using_converts_to: Some(component_stack(Vec::new())),
+            effects: vec![PossibleEffect {
+                effect: EffectInstance {
+                    effect: VarInt(1),
+                    details: details(1, Some(Box::new(details(0, None)))),
+                },
+                probability: 0.5,
+            }],
+        }
+    }
+
+    #[test]
+    fn slots_changed_in_1_20_5() {
+        for version in [ProtocolVersion::V1_19_3, ProtocolVersion::V1_20_3] {
+            assert_eq!(round_trip(&Slot(None), version), Slot(None));
+            assert_eq!(round_trip(&nbt_stack(), version), nbt_stack());
+            assert!(component_stack(Vec::new())
+                .encode_versioned(&mut Vec::new(), version)
+                .is_err());
+
+            // the count was a byte back then.
+            let mut stack = nbt_stack();
+            stack.0.as_mut().unwrap().count = 128;
+            assert!(stack.encode_versioned(&mut Vec::new(), version).is_err());
+        }
+
+        let stack = component_stack(vec![
+            ItemComponent::Damage(VarInt(4)),
+            ItemComponent::CustomName("Sword".into()),
+            ItemComponent::Unbreakable {
+                show_in_tooltip: false,
+            },
+        ]);
+
+        for version in [ProtocolVersion::V1_20_5, ProtocolVersion::V1_21] {
+            assert_eq!(round_trip(&stack, version), stack);
+            assert!(nbt_stack()
+                .encode_versioned(&mut Vec::new(), version)
+                .is_err());
+        }
+
+        let mut buf = Vec::new();
+        Slot(None)
+            .encode_versioned(&mut buf, ProtocolVersion::V1_21)
+            .unwrap();
+        assert_eq!(buf, [0]);
+    }
+
+    #[test]
+    fn components_round_trip() {
+        let components = [
+            ItemComponent::CustomData(nbt!({ "id": 3i32 })),
+            ItemComponent::Lore(vec!["a".into(), "b".into()]),
+            ItemComponent::Rarity(Rarity::Epic),
+            ItemComponent::Enchantments(Enchantments {
+                enchantments: vec![Enchantment {
+                    id: VarInt(3),
+                    level: VarInt(2),
+                }],
+                show_in_tooltip: true,
+            }),
+            ItemComponent::CanBreak(AdventureModePredicate {
+                predicates: vec![BlockPredicate {
+                    blocks: Some(IdSet::Tag("minecraft:logs".into())),
+                    properties: Some(vec![PropertyMatcher {
+                        name: "age".into(),
+                        value: PropertyValue::Range {
+                            min: Some("1".into()),
+                            max: None,
+                        },
+                    }]),
+                    nbt: None,
+                }],
+                show_in_tooltip: false,
+            }),
+            ItemComponent::HideTooltip,
+            ItemComponent::IntangibleProjectile,
+            ItemComponent::Tool(Tool {
+                rules: vec![ToolRule {
+                    blocks: IdSet::Ids(vec![VarInt(1)]),
+                    speed: Some(4.0),
+                    correct_for_drops: None,
+                }],
+                default_mining_speed: 1.0,
+                damage_per_block: VarInt(1),
+            }),
+            ItemComponent::Container(vec![Slot(None), component_stack(Vec::new())]),
+            ItemComponent::Trim(Box::new(ArmorTrim {
+                material: RegistryReference::Id(VarInt(0)),
+                pattern: RegistryReference::Inline(TrimPattern {
+                    asset_id: "minecraft:coast".into(),
+                    template_item: VarInt(9),
+                    description: "Coast".into(),
+                    decal: false,
+                }),
+                show_in_tooltip: true,
+            })),
+            ItemComponent::LodestoneTracker {
+                target: Some(GlobalPosition {
+                    dimension: "minecraft:overworld".into(),
+                    location: Position { x: 1, y: 2, z: 3 },
+                }),
+                tracked: true,
+            },
+            ItemComponent::Profile(ResolvableProfile {
+                name: Some("Notch".into()),
+                uuid: None,
+                properties: Vec::new(),
+            }),
+        ];
+
+        for version in [ProtocolVersion::V1_20_5, ProtocolVersion::V1_21] {
+            for component in &components {
+                assert_eq!(&round_trip(component, version), component);
+            }
+        }
+
+        // the item a food turns into is only sent since 1.21.
+        let component = ItemComponent::Food(Box::new(food()));
+        assert_eq!(round_trip(&component, ProtocolVersion::V1_21), component);
+        assert_eq!(
+            round_trip(&component, ProtocolVersion::V1_20_5),
+            ItemComponent::Food(Box::new(Food {
+                using_converts_to: None,
+                ..food()
+            }))
+        );
+    }
+
+    #[test]
+    fn component_ids_depend_on_the_version() {
+        let song = ItemComponent::JukeboxPlayable(Box::new(JukeboxPlayable {
+            song: JukeboxSongReference::Name("minecraft:cat".into()),
+            show_in_tooltip: true,
+        }));
+
+        assert_eq!(song.id(ProtocolVersion::V1_21).unwrap(), VarInt(42));
+        assert_eq!(round_trip(&song, ProtocolVersion::V1_21), song);
+        assert!(song.id(ProtocolVersion::V1_20_5).is_err());
+
+        assert_eq!(
+            component_name(42, ProtocolVersion::V1_20_5),
+            Some("minecraft:recipes")
+        );
+        assert_eq!(
+            component_name(43, ProtocolVersion::V1_21),
+            Some("minecraft:recipes")
+        );
+        assert_eq!(
+            component_name(56, ProtocolVersion::V1_21),
+            Some("minecraft:container_loot")
+        );
+        assert_eq!(component_name(57, ProtocolVersion::V1_21), None);
+        assert_eq!(
+            component_id("minecraft:damage", ProtocolVersion::V1_19_3),
+            None
+        );
+
+        // 1.21.2 only keeps the ids of the first components.
+        let damage = ItemComponent::Damage(VarInt(1));
+        assert_eq!(round_trip(&damage, ProtocolVersion::V1_21_2), damage);
+        assert!(ItemComponent::Lore(Vec::new())
+            .id(ProtocolVersion::V1_21_2)
+            .is_err());
+        assert!(
+            ItemComponent::decode_versioned(&mut &[7u8, 0][..], ProtocolVersion::V1_21_2).is_err()
+        );
+    }
+
+    #[test]
+    fn nesting_is_limited() {
+        // bundles in bundles, each holding a single item.
+        let nested = |depth| {
+            let mut buf = Vec::new();
+            for _ in 0..depth {
+                buf.extend([1, 1, 1, 0, 30, 1]);
+            }
+            buf.push(0);
+            buf
+        };
+
+        assert!(
+            Slot::decode_versioned(&mut &nested(MAX_DEPTH)[..], ProtocolVersion::V1_21).is_ok()
+        );
+        assert!(
+            Slot::decode_versioned(&mut &nested(MAX_DEPTH + 1)[..], ProtocolVersion::V1_21)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use std::io::{Read, Write};
+
+use proc_macros::MinecraftPacket;
+
+use crate::{
+    identifier::{Identifier, RegistryReference},
+    packets::{Decodable, Encodable},
+    VarInt,
+};
+
+// the volume slider a sound is played with.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum SoundCategory {
+    Master = 0,
+    Music = 1,
+    Records = 2,
+    Weather = 3,
+    Blocks = 4,
+    Hostile = 5,
+    Neutral = 6,
+    Players = 7,
+    Ambient = 8,
+    Voice = 9,
+}
+
+impl Decodable for SoundCategory {
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        let category = VarInt::decode(reader)?;
+
+        Ok(match category.0 {
+            0 => Self::Master,
+            1 => Self::Music,
+            2 => Self::Records,
+            3 => Self::Weather,
+            4 => Self::Blocks,
+            5 => Self::Hostile,
+            6 => Self::Neutral,
+            7 => Self::Players,
+            8 => Self::Ambient,
+            9 => Self::Voice,
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid sound category, {}", category.0),
+            ))?,
+        })
+    }
+}
+
+impl Encodable for SoundCategory {
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        VarInt(*self as i32).encode(writer)
+    }
+}
+
+// a sound that isn't in the client's registry, e.g. from a resource pack. without a fixed range the range depends on
+// the volume.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct SoundEvent {
+    pub name: Identifier,
+    pub fixed_range: Option<f32>,
+}
+
+// an entry of the sound event registry, or a sound event sent inline.
+pub type SoundReference = RegistryReference<SoundEvent>;
@@ -0,0 +1,69 @@
+use proc_macros::MinecraftPacket;
+use types::packet::ProtocolVersion;
+
+use crate::{
+    identifier::Identifier,
+    packets::{Decodable, Encodable, VersionedDecodable, VersionedEncodable},
+    position::Position,
+    VarInt,
+};
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct DeathLocation {
+    pub dimension_name: Identifier,
+    pub location: Position,
+}
+
+// sent by name before 1.20.5, since then it's the id in the dimension type registry.
+#[derive(Debug, PartialEq, Clone)]
+pub enum DimensionType {
+    Name(Identifier),
+    Id(VarInt),
+}
+
+impl VersionedDecodable for DimensionType {
+    fn decode_versioned<R: std::io::Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        if version < ProtocolVersion::V1_20_5 {
+            return Ok(Self::Name(Identifier::decode(reader)?));
+        }
+
+        Ok(Self::Id(VarInt::decode(reader)?))
+    }
+}
+
+impl VersionedEncodable for DimensionType {
+    fn encode_versioned<W: std::io::Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        match (self, version < ProtocolVersion::V1_20_5) {
+            (Self::Name(name), true) => name.encode(writer),
+            (Self::Id(id), false) => id.encode(writer),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "The dimension type is sent by name before 1.20.5 and by id since.",
+            )),
+        }
+    }
+}
+
+// the world the player spawns into, shared by Login (play) and Respawn since 1.20.2.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct SpawnInfo {
+    #[versioned]
+    pub dimension_type: DimensionType,
+    pub dimension_name: Identifier,
+    pub hashed_seed: i64,
+    pub game_mode: u8,
+    // -1 when there is no previous game mode.
+    pub previous_game_mode: i8,
+    pub is_debug: bool,
+    pub is_flat: bool,
+    pub death_location: Option<DeathLocation>,
+    #[since(V1_20)]
+    pub portal_cooldown: Option<VarInt>,
+}
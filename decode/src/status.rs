@@ -0,0 +1,270 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    datatypes::json::{decode_json, encode_json},
+    packets::{Decodable, Encodable},
+    text::TextComponent,
+};
+
+const FAVICON_PREFIX: &str = "data:image/png;base64,";
+const FAVICON_SIZE: u32 = 64;
+
+// the JSON document sent in StatusResponse, which the client shows in its server list.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerStatus {
+    pub version: StatusVersion,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub players: Option<StatusPlayers>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<TextComponent>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub favicon: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enforces_secure_chat: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previews_chat: Option<bool>,
+    // added by Forge 1.13 and newer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forge_data: Option<ForgeData>,
+    // added by Forge before 1.13.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modinfo: Option<LegacyForgeData>,
+    // added by NeoForge.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_modded: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusVersion {
+    pub name: String,
+    pub protocol: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusPlayers {
+    pub max: i32,
+    pub online: i32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sample: Vec<PlayerSample>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerSample {
+    pub name: String,
+    pub id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForgeData {
+    #[serde(default)]
+    pub channels: Vec<ForgeChannel>,
+    #[serde(default)]
+    pub mods: Vec<ForgeMod>,
+    pub fml_network_version: i32,
+    #[serde(default)]
+    pub truncated: bool,
+    // since FML3 the channels and mods are packed into this string instead.
+    #[serde(default, rename = "d", skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForgeChannel {
+    pub res: String,
+    pub version: String,
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForgeMod {
+    #[serde(rename = "modId")]
+    pub mod_id: String,
+    #[serde(rename = "modmarker")]
+    pub mod_marker: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LegacyForgeData {
+    #[serde(rename = "type")]
+    pub ty: String,
+    #[serde(rename = "modList")]
+    pub mod_list: Vec<LegacyForgeMod>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LegacyForgeMod {
+    #[serde(rename = "modid")]
+    pub mod_id: String,
+    pub version: String,
+}
+
+impl ServerStatus {
+    /// Returns the PNG bytes of the favicon, if there is one.
+    pub fn favicon_png(&self) -> Result<Option<Vec<u8>>, std::io::Error> {
+        self.favicon.as_deref().map(decode_favicon).transpose()
+    }
+
+    pub fn set_favicon_png(&mut self, png: &[u8]) -> Result<(), std::io::Error> {
+        self.favicon = Some(encode_favicon(png)?);
+        Ok(())
+    }
+}
+
+/// Decodes a `data:image/png;base64,...` favicon, making sure it is a 64x64 PNG.
+pub fn decode_favicon(favicon: &str) -> Result<Vec<u8>, std::io::Error> {
+    let data = favicon.strip_prefix(FAVICON_PREFIX).ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "Favicon is not a base64 encoded PNG.",
+        )
+    })?;
+
+    // older servers wrap the base64 data over multiple lines.
+    let data = data
+        .chars()
+        .filter(|ch| !ch.is_ascii_whitespace())
+        .collect::<String>();
+
+    let png = STANDARD
+        .decode(data)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+    validate_favicon(&png)?;
+
+    Ok(png)
+}
+
+/// Encodes a 64x64 PNG as a `data:image/png;base64,...` favicon.
+pub fn encode_favicon(png: &[u8]) -> Result<String, std::io::Error> {
+    validate_favicon(png)?;
+
+    Ok(format!("{}{}", FAVICON_PREFIX, STANDARD.encode(png)))
+}
+
+// we only look at the IHDR chunk, which the PNG spec requires to come first.
+fn validate_favicon(png: &[u8]) -> Result<(), std::io::Error> {
+    const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+
+    if png.len() < 24 || png[..8] != SIGNATURE || &png[12..16] != b"IHDR" {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "Favicon is not a PNG.",
+        ));
+    }
+
+    let width = u32::from_be_bytes([png[16], png[17], png[18], png[19]]);
+    let height = u32::from_be_bytes([png[20], png[21], png[22], png[23]]);
+
+    if width != FAVICON_SIZE || height != FAVICON_SIZE {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Favicon has to be 64x64, but is {}x{}", width, height),
+        ));
+    }
+
+    Ok(())
+}
+
+impl Decodable for ServerStatus {
+    fn decode<R: std::io::Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        decode_json(reader)
+    }
+}
+
+impl Encodable for ServerStatus {
+    fn encode<W: std::io::Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        encode_json(self, writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    // just the signature and the IHDR chunk, which is all the validation looks at.
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut png = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+        png.extend(13u32.to_be_bytes());
+        png.extend(b"IHDR");
+        png.extend(width.to_be_bytes());
+        png.extend(height.to_be_bytes());
+        png.extend([8, 6, 0, 0, 0]);
+        png
+    }
+
+    #[test]
+    fn favicon() {
+        let favicon = encode_favicon(&png(64, 64)).unwrap();
+        assert!(favicon.starts_with(FAVICON_PREFIX));
+        assert_eq!(decode_favicon(&favicon).unwrap(), png(64, 64));
+
+        let (prefix, data) = favicon.split_at(FAVICON_PREFIX.len() + 8);
+        assert_eq!(
+            decode_favicon(&format!("{}\n{}", prefix, data)).unwrap(),
+            png(64, 64)
+        );
+
+        assert!(encode_favicon(&png(32, 64)).is_err());
+        assert!(encode_favicon(b"not a png").is_err());
+        assert!(decode_favicon(&encode_favicon(&png(64, 64)).unwrap()[5..]).is_err());
+        assert!(decode_favicon(&format!("{}!!", FAVICON_PREFIX)).is_err());
+        assert!(decode_favicon(&format!(
+            "{}{}",
+            FAVICON_PREFIX,
+            STANDARD.encode(png(128, 128))
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn status_json() {
+        let json = json!({
+            "version": { "name": "1.21", "protocol": 767 },
+            "players": {
+                "max": 20,
+                "online": 1,
+                "sample": [{ "name": "Steve", "id": "069a79f4-44e9-4726-a5be-fca90e38aaf5" }],
+            },
+            "description": { "text": "A Minecraft Server" },
+            "enforcesSecureChat": true,
+            "forgeData": { "fmlNetworkVersion": 3, "d": "abc" },
+            "isModded": true,
+        });
+
+        let mut status = serde_json::from_value::<ServerStatus>(json).unwrap();
+        assert_eq!(status.version.protocol, 767);
+        assert_eq!(status.players.as_ref().unwrap().sample[0].name, "Steve");
+        assert_eq!(
+            status.description,
+            Some(TextComponent::text("A Minecraft Server"))
+        );
+        assert_eq!(
+            status.forge_data.as_ref().unwrap().data.as_deref(),
+            Some("abc")
+        );
+        assert_eq!(status.favicon_png().unwrap(), None);
+
+        status.set_favicon_png(&png(64, 64)).unwrap();
+        assert_eq!(status.favicon_png().unwrap(), Some(png(64, 64)));
+
+        let mut bytes = Vec::new();
+        status.encode(&mut bytes).unwrap();
+        assert_eq!(ServerStatus::decode(&mut bytes.as_slice()).unwrap(), status);
+
+        // old servers send the description as a plain string and leave out everything optional.
+        let status = serde_json::from_value::<ServerStatus>(json!({
+            "version": { "name": "1.8.9", "protocol": 47 },
+            "description": "A Minecraft Server",
+            "modinfo": { "type": "FML", "modList": [{ "modid": "forge", "version": "11.15" }] },
+        }))
+        .unwrap();
+        assert_eq!(status.players, None);
+        assert_eq!(status.modinfo.unwrap().mod_list[0].mod_id, "forge");
+    }
+}
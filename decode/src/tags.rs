@@ -0,0 +1,16 @@
+use proc_macros::MinecraftPacket;
+
+use crate::{identifier::Identifier, VarInt};
+
+// a tag like minecraft:logs with the ids of its entries in the registry.
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct Tag {
+    pub name: Identifier,
+    pub entries: Vec<VarInt>,
+}
+
+#[derive(MinecraftPacket, Debug, PartialEq, Clone)]
+pub struct RegistryTags {
+    pub registry: Identifier,
+    pub tags: Vec<Tag>,
+}
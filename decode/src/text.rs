@@ -0,0 +1,479 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use types::packet::ProtocolVersion;
+
+use crate::{
+    datatypes::json::{decode_json, encode_json},
+    nbt,
+    packets::{Decodable, Encodable, VersionedDecodable, VersionedEncodable},
+};
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct TextComponent {
+    #[serde(flatten)]
+    pub content: TextContent,
+    #[serde(flatten)]
+    pub style: Style,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra: Vec<TextComponent>,
+}
+
+// the order matters here, a component without any of the other keys is treated as plain text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TextContent {
+    Translatable {
+        translate: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        fallback: Option<String>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        with: Vec<TextComponent>,
+    },
+    Keybind {
+        keybind: String,
+    },
+    Score {
+        score: Score,
+    },
+    Selector {
+        selector: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        separator: Option<Box<TextComponent>>,
+    },
+    Text {
+        #[serde(default)]
+        text: String,
+    },
+}
+
+impl Default for TextContent {
+    fn default() -> Self {
+        TextContent::Text {
+            text: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Score {
+    pub name: String,
+    pub objective: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Style {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient_bool"
+    )]
+    pub bold: Option<bool>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient_bool"
+    )]
+    pub italic: Option<bool>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient_bool"
+    )]
+    pub underlined: Option<bool>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient_bool"
+    )]
+    pub strikethrough: Option<bool>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "lenient_bool"
+    )]
+    pub obfuscated: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub font: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub insertion: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub click_event: Option<ClickEvent>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hover_event: Option<HoverEvent>,
+}
+
+// NBT has no booleans, so components sent as NBT use bytes for them.
+fn lenient_bool<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<bool>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        Bool(bool),
+        Number(i64),
+    }
+
+    Ok(
+        Option::<Repr>::deserialize(deserializer)?.map(|value| match value {
+            Repr::Bool(value) => value,
+            Repr::Number(value) => value != 0,
+        }),
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClickEvent {
+    pub action: String,
+    pub value: String,
+}
+
+// the contents depend on the action (text, item or entity), so we keep them as plain JSON.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HoverEvent {
+    pub action: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contents: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<serde_json::Value>,
+}
+
+impl TextComponent {
+    pub fn text<S: Into<String>>(text: S) -> Self {
+        Self {
+            content: TextContent::Text { text: text.into() },
+            ..Default::default()
+        }
+    }
+
+    pub fn translate<S: Into<String>>(translate: S, with: Vec<TextComponent>) -> Self {
+        Self {
+            content: TextContent::Translatable {
+                translate: translate.into(),
+                fallback: None,
+                with,
+            },
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&str> for TextComponent {
+    fn from(text: &str) -> Self {
+        Self::text(text)
+    }
+}
+
+impl From<String> for TextComponent {
+    fn from(text: String) -> Self {
+        Self::text(text)
+    }
+}
+
+// the text of this component and all of its children, without any formatting. translations are shown as their key.
+impl Display for TextComponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.content {
+            TextContent::Text { text } => write!(f, "{}", text)?,
+            TextContent::Translatable {
+                translate,
+                fallback,
+                ..
+            } => write!(f, "{}", fallback.as_ref().unwrap_or(translate))?,
+            TextContent::Keybind { keybind } => write!(f, "{}", keybind)?,
+            TextContent::Score { score } => write!(f, "{}", score.name)?,
+            TextContent::Selector { selector, .. } => write!(f, "{}", selector)?,
+        }
+
+        self.extra
+            .iter()
+            .try_for_each(|extra| write!(f, "{}", extra))
+    }
+}
+
+// besides objects, a component can be a plain string, a number or a boolean (all shown as text), or an array where the
+// first element is the parent of the others.
+impl<'de> Deserialize<'de> for TextComponent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Object {
+            #[serde(flatten)]
+            content: TextContent,
+            #[serde(flatten)]
+            style: Style,
+            #[serde(default)]
+            extra: Vec<TextComponent>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Text(String),
+            Number(serde_json::Number),
+            Bool(bool),
+            List(Vec<TextComponent>),
+            Object(Box<Object>),
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Text(text) => TextComponent::text(text),
+            Repr::Number(number) => TextComponent::text(number.to_string()),
+            Repr::Bool(value) => TextComponent::text(value.to_string()),
+            Repr::List(list) => {
+                let mut list = list.into_iter();
+                let mut component = list
+                    .next()
+                    .ok_or_else(|| serde::de::Error::custom("Empty text component list."))?;
+
+                component.extra.extend(list);
+                component
+            }
+            Repr::Object(object) => TextComponent {
+                content: object.content,
+                style: object.style,
+                extra: object.extra,
+            },
+        })
+    }
+}
+
+// without a version a text component is a JSON string, which is what the login state always uses.
+impl Decodable for TextComponent {
+    fn decode<R: std::io::Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        decode_json(reader)
+    }
+}
+
+impl Encodable for TextComponent {
+    fn encode<W: std::io::Write>(&self, writer: &mut W) -> Result<(), std::io::Error> {
+        encode_json(self, writer)
+    }
+}
+
+// since 1.20.3 the other states send text components as NBT.
+impl VersionedDecodable for TextComponent {
+    fn decode_versioned<R: std::io::Read>(
+        reader: &mut R,
+        version: ProtocolVersion,
+    ) -> Result<Self, std::io::Error> {
+        if version < ProtocolVersion::V1_20_3 {
+            return Self::decode(reader);
+        }
+
+        let value = nbt::Value::decode(reader)?;
+
+        serde_json::from_value(unwrap_mixed_lists(value.to_json()))
+            .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+    }
+}
+
+// NBT lists hold a single tag type, so vanilla wraps the elements of mixed lists as `{"": value}` compounds.
+fn is_wrapper(object: &serde_json::Map<String, serde_json::Value>) -> bool {
+    object.len() == 1 && object.contains_key("")
+}
+
+fn unwrap_mixed_lists(json: serde_json::Value) -> serde_json::Value {
+    match json {
+        serde_json::Value::Array(values) => values
+            .into_iter()
+            .map(|value| match value {
+                serde_json::Value::Object(mut object) if is_wrapper(&object) => {
+                    unwrap_mixed_lists(object.remove("").unwrap_or_default())
+                }
+                value => unwrap_mixed_lists(value),
+            })
+            .collect(),
+        serde_json::Value::Object(object) => object
+            .into_iter()
+            .map(|(key, value)| (key, unwrap_mixed_lists(value)))
+            .collect(),
+        json => json,
+    }
+}
+
+// the reverse of `unwrap_mixed_lists`. NBT has no null either, so null values are left out.
+fn to_nbt(json: &serde_json::Value) -> Result<nbt::Value, nbt::NbtError> {
+    match json {
+        serde_json::Value::Array(values) => {
+            let values = values
+                .iter()
+                .filter(|value| !value.is_null())
+                .collect::<Vec<_>>();
+
+            if values.iter().all(|value| value.is_number()) {
+                return nbt::Value::from_json(&values.into_iter().cloned().collect());
+            }
+
+            let list = values
+                .into_iter()
+                .map(to_nbt)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if list.iter().all(|value| value.tag_id() == list[0].tag_id()) {
+                return Ok(nbt::Value::List(list));
+            }
+
+            Ok(nbt::Value::List(
+                list.into_iter()
+                    .map(|value| match value {
+                        nbt::Value::Compound(compound)
+                            if !(compound.len() == 1 && compound.contains_key("")) =>
+                        {
+                            nbt::Value::Compound(compound)
+                        }
+                        value => {
+                            nbt::Value::Compound(nbt::Compound::from([(String::new(), value)]))
+                        }
+                    })
+                    .collect(),
+            ))
+        }
+        serde_json::Value::Object(object) => Ok(nbt::Value::Compound(
+            object
+                .iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| Ok((key.clone(), to_nbt(value)?)))
+                .collect::<Result<nbt::Compound, nbt::NbtError>>()?,
+        )),
+        json => nbt::Value::from_json(json),
+    }
+}
+
+impl VersionedEncodable for TextComponent {
+    fn encode_versioned<W: std::io::Write>(
+        &self,
+        writer: &mut W,
+        version: ProtocolVersion,
+    ) -> Result<(), std::io::Error> {
+        if version < ProtocolVersion::V1_20_3 {
+            return self.encode(writer);
+        }
+
+        let json = serde_json::to_value(self)
+            .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidInput, error))?;
+
+        to_nbt(&json)?.encode(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn round_trip(component: &TextComponent, version: ProtocolVersion) -> TextComponent {
+        let mut bytes = Vec::new();
+        component.encode_versioned(&mut bytes, version).unwrap();
+
+        let mut reader = bytes.as_slice();
+        let decoded = TextComponent::decode_versioned(&mut reader, version).unwrap();
+        assert!(reader.is_empty());
+        decoded
+    }
+
+    fn component() -> TextComponent {
+        let mut component = TextComponent::translate("chat.type.text", vec!["Steve".into()]);
+        component.style.bold = Some(true);
+        component.style.color = Some("gold".into());
+        component.extra.push(TextComponent::text("!"));
+        component
+    }
+
+    #[test]
+    fn json_until_1_20_3() {
+        let mut bytes = Vec::new();
+        component()
+            .encode_versioned(&mut bytes, ProtocolVersion::V1_20_2)
+            .unwrap();
+
+        let mut reader = bytes.as_slice();
+        let json = String::decode(&mut reader).unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&json).unwrap(),
+            json!({
+                "translate": "chat.type.text",
+                "with": [{ "text": "Steve" }],
+                "color": "gold",
+                "bold": true,
+                "extra": [{ "text": "!" }],
+            })
+        );
+
+        assert_eq!(
+            round_trip(&component(), ProtocolVersion::V1_20_2),
+            component()
+        );
+    }
+
+    #[test]
+    fn nbt_since_1_20_3() {
+        let mut bytes = Vec::new();
+        component()
+            .encode_versioned(&mut bytes, ProtocolVersion::V1_20_3)
+            .unwrap();
+
+        let value = nbt::Value::decode(&mut bytes.as_slice()).unwrap();
+        assert_eq!(value.get_str("translate").unwrap(), "chat.type.text");
+        assert_eq!(value.get_i8("bold").unwrap(), 1);
+
+        assert_eq!(
+            round_trip(&component(), ProtocolVersion::V1_20_3),
+            component()
+        );
+    }
+
+    #[test]
+    fn mixed_lists_are_wrapped() {
+        // how vanilla writes `["a", {"text": "b"}]` as NBT.
+        let mut bytes = Vec::new();
+        nbt!({
+            "text": "",
+            "extra": [{ "": "a" }, { "text": "b" }, { "": 1i32 }],
+        })
+        .encode_versioned(&mut bytes, ProtocolVersion::V1_20_3)
+        .unwrap();
+
+        let decoded =
+            TextComponent::decode_versioned(&mut bytes.as_slice(), ProtocolVersion::V1_20_3)
+                .unwrap();
+        assert_eq!(
+            decoded.extra,
+            vec![
+                TextComponent::text("a"),
+                TextComponent::text("b"),
+                TextComponent::text("1")
+            ]
+        );
+
+        let mut component = TextComponent::text("item");
+        component.style.hover_event = Some(HoverEvent {
+            action: "show_item".into(),
+            contents: Some(
+                json!({ "id": "minecraft:stone", "tag": null, "lore": ["a", 1, { "": 2 }] }),
+            ),
+            value: None,
+        });
+
+        let mut bytes = Vec::new();
+        component
+            .encode_versioned(&mut bytes, ProtocolVersion::V1_20_3)
+            .unwrap();
+
+        let value = nbt::Value::decode(&mut bytes.as_slice()).unwrap();
+        let contents = value.get("hoverEvent").unwrap().get("contents").unwrap();
+        assert!(contents.get("tag").is_err());
+        assert_eq!(
+            contents.get("lore").unwrap(),
+            &nbt!([{ "": "a" }, { "": 1i32 }, { "": { "": 2i32 } }])
+        );
+
+        let decoded = round_trip(&component, ProtocolVersion::V1_20_3);
+        assert_eq!(
+            decoded.style.hover_event.unwrap().contents.unwrap(),
+            json!({ "id": "minecraft:stone", "lore": ["a", 1, { "": 2 }] })
+        );
+    }
+}
@@ -2,25 +2,53 @@ use std::collections::HashMap;
 
 use proc_macro::TokenStream;
 use quote::{quote, ToTokens};
-use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, Type};
-use types::packet::{ClientState, PacketDirection, PacketMacroData};
+use syn::{
+    bracketed,
+    parse::{Parse, ParseStream},
+    parse_macro_input, Data, DeriveInput, Field, Fields, GenericArgument, Ident, LitInt,
+    PathArguments, Token, Type,
+};
+use types::packet::{ClientState, PacketDirection, PacketMacroData, ProtocolVersion};
+
+// version gated fields have to be an Option<T>, this returns the T.
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(path) => path.path.segments.last()?,
+        _ => None?,
+    };
+
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => match arguments.args.first()? {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
 
-#[proc_macro_derive(MinecraftPacket)]
+fn version_attribute(field: &Field, name: &str) -> syn::Result<Option<Ident>> {
+    field
+        .attrs
+        .iter()
+        .find(|attribute| attribute.path.is_ident(name))
+        .map(|attribute| attribute.parse_args::<Ident>())
+        .transpose()
+}
+
+// fields can be annotated with #[since(V1_20_5)] and #[until(V1_21_2)] when they only exist in some protocol versions,
+// and with #[versioned] when their own encoding depends on the protocol version.
+#[proc_macro_derive(MinecraftPacket, attributes(since, until, versioned))]
 pub fn define_packet(input: TokenStream) -> TokenStream {
     // Parse the input token stream and extract the struct name and fields
     let input = parse_macro_input!(input as DeriveInput);
     let name = input.ident;
     let fields = match input.data {
         Data::Struct(data) => match data.fields {
-            Fields::Named(fields) => fields
-                .named
-                .iter()
-                .map(|field| {
-                    let field_name = field.ident.as_ref().unwrap();
-                    let field_type = &field.ty;
-                    (field_name.clone(), field_type.clone())
-                })
-                .collect::<Vec<(Ident, Type)>>(),
+            Fields::Named(fields) => fields.named.into_iter().collect::<Vec<Field>>(),
             _ => panic!("Unsupported field format for Packet derive macro"),
         },
         _ => panic!("Unsupported data format for Packet derive macro"),
@@ -28,59 +56,199 @@ pub fn define_packet(input: TokenStream) -> TokenStream {
 
     let mut encode_expand = quote! {};
     let mut decode_expand = quote! {};
+    let mut is_versioned = false;
 
-    let mut segments_combined = String::from("");
+    for field in &fields {
+        let field_name = field.ident.as_ref().unwrap();
 
-    for (field_name, field_type) in fields {
-        if let Type::Path(path) = &field_type {
-            let segments = path.path.segments.to_token_stream().to_string();
-            segments_combined = segments_combined + &segments;
+        let (since, until) = match (
+            version_attribute(field, "since"),
+            version_attribute(field, "until"),
+        ) {
+            (Ok(since), Ok(until)) => (since, until),
+            (Err(error), _) | (_, Err(error)) => return error.to_compile_error().into(),
+        };
 
-            // this is for types that contain a generic type, e.g. Option<T>. this will then
-            // call the decode function through
-            // Option::<T>::decode(R);
-            if segments.contains("<") {
-                let types = segments.split("<").collect::<Vec<&str>>();
+        let versioned = field
+            .attrs
+            .iter()
+            .any(|attribute| attribute.path.is_ident("versioned"));
+
+        let condition = match (&since, &until) {
+            (Some(since), Some(until)) => Some(quote! {
+                version >= types::packet::ProtocolVersion::#since
+                    && version < types::packet::ProtocolVersion::#until
+            }),
+            (Some(since), None) => Some(quote! {
+                version >= types::packet::ProtocolVersion::#since
+            }),
+            (None, Some(until)) => Some(quote! {
+                version < types::packet::ProtocolVersion::#until
+            }),
+            (None, None) => None,
+        };
 
-                let enum_ty = types[0].parse::<proc_macro2::TokenStream>().unwrap();
-                let data_ty = types[1]
-                    .replace(">", "")
-                    .parse::<proc_macro2::TokenStream>()
-                    .unwrap();
+        is_versioned |= versioned || condition.is_some();
+
+        let field_type =
+            match &condition {
+                Some(_) => match option_inner_type(&field.ty) {
+                    Some(ty) => ty,
+                    None => return syn::Error::new_spanned(
+                        &field.ty,
+                        "Fields that only exist in some protocol versions have to be an Option!",
+                    )
+                    .to_compile_error()
+                    .into(),
+                },
+                None => &field.ty,
+            };
+
+        let (decode_value, encode_value) = if versioned {
+            (
+                quote! {
+                    <#field_type as crate::packets::VersionedDecodable>::decode_versioned(reader, version)?
+                },
+                quote! {
+                    crate::packets::VersionedEncodable::encode_versioned(value, writer, version)?;
+                },
+            )
+        } else {
+            (
+                quote! {
+                    <#field_type as crate::packets::Decodable>::decode(reader)?
+                },
+                quote! {
+                    crate::packets::Encodable::encode(value, writer)?;
+                },
+            )
+        };
+
+        match condition {
+            Some(condition) => {
+                let missing = format!("{} is required by this protocol version.", field_name);
 
                 decode_expand.extend(quote! {
-                    #field_name: #enum_ty::<#data_ty>::decode(reader)?,
+                    #field_name: if #condition {
+                        Some(#decode_value)
+                    } else {
+                        None
+                    },
                 });
-            } else {
+                encode_expand.extend(quote! {
+                    if #condition {
+                        match &self.#field_name {
+                            Some(value) => {
+                                #encode_value
+                            }
+                            None => Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, #missing))?,
+                        }
+                    }
+                });
+            }
+            None => {
                 decode_expand.extend(quote! {
-                    #field_name: #field_type::decode(reader)?,
+                    #field_name: #decode_value,
+                });
+                encode_expand.extend(quote! {
+                    {
+                        let value = &self.#field_name;
+                        #encode_value
+                    }
                 });
             }
-            encode_expand.extend(quote! {
-                self.#field_name.encode(writer)?;
-            });
         }
     }
 
-    // Generate the implementation of the encode and decode methods
-    let expanded = quote! {
-        impl crate::packets::Encodable for #name {
-            fn encode<W: std::io::Write>(
-                &self,
-                writer: &mut W
-            ) -> Result<(), std::io::Error> {
-                #encode_expand
-                Ok(())
+    // Generate the implementation of the encode and decode methods. packets that don't depend on the protocol
+    // version ignore it, the others use the latest version when decoded without one.
+    let expanded = if is_versioned {
+        quote! {
+            impl crate::packets::Encodable for #name {
+                fn encode<W: std::io::Write>(
+                    &self,
+                    writer: &mut W
+                ) -> Result<(), std::io::Error> {
+                    crate::packets::VersionedEncodable::encode_versioned(
+                        self,
+                        writer,
+                        types::packet::ProtocolVersion::LATEST,
+                    )
+                }
+            }
+
+            impl crate::packets::Decodable for #name {
+                fn decode<R: std::io::Read>(
+                    reader: &mut R
+                ) -> Result<Self, std::io::Error> {
+                    crate::packets::VersionedDecodable::decode_versioned(
+                        reader,
+                        types::packet::ProtocolVersion::LATEST,
+                    )
+                }
+            }
+
+            impl crate::packets::VersionedEncodable for #name {
+                fn encode_versioned<W: std::io::Write>(
+                    &self,
+                    writer: &mut W,
+                    version: types::packet::ProtocolVersion
+                ) -> Result<(), std::io::Error> {
+                    #encode_expand
+                    Ok(())
+                }
+            }
+
+            impl crate::packets::VersionedDecodable for #name {
+                fn decode_versioned<R: std::io::Read>(
+                    reader: &mut R,
+                    version: types::packet::ProtocolVersion
+                ) -> Result<Self, std::io::Error> {
+                    Ok(Self {
+                        #decode_expand
+                    })
+                }
             }
         }
+    } else {
+        quote! {
+            impl crate::packets::Encodable for #name {
+                fn encode<W: std::io::Write>(
+                    &self,
+                    writer: &mut W
+                ) -> Result<(), std::io::Error> {
+                    #encode_expand
+                    Ok(())
+                }
+            }
 
-        impl crate::packets::Decodable for #name {
-            fn decode<R: std::io::Read>(
-                reader: &mut R
-            ) -> Result<Self, std::io::Error> {
-                Ok(Self {
-                    #decode_expand
-                })
+            impl crate::packets::Decodable for #name {
+                fn decode<R: std::io::Read>(
+                    reader: &mut R
+                ) -> Result<Self, std::io::Error> {
+                    Ok(Self {
+                        #decode_expand
+                    })
+                }
+            }
+
+            impl crate::packets::VersionedEncodable for #name {
+                fn encode_versioned<W: std::io::Write>(
+                    &self,
+                    writer: &mut W,
+                    _version: types::packet::ProtocolVersion
+                ) -> Result<(), std::io::Error> {
+                    crate::packets::Encodable::encode(self, writer)
+                }
+            }
+
+            impl crate::packets::VersionedDecodable for #name {
+                fn decode_versioned<R: std::io::Read>(
+                    reader: &mut R,
+                    _version: types::packet::ProtocolVersion
+                ) -> Result<Self, std::io::Error> {
+                    crate::packets::Decodable::decode(reader)
+                }
             }
         }
     };
@@ -88,171 +256,506 @@ pub fn define_packet(input: TokenStream) -> TokenStream {
     expanded.into()
 }
 
+// either a single id for every protocol version, or a list like [V1_19_3 = 0x24, V1_21 = 0x2B] where every id is
+// used from its version until the next entry. V1_19_3..V1_20_2 = 0x02 ends the packet at a version instead.
+enum PacketIds {
+    Fixed(LitInt),
+    Versioned(Vec<VersionedId>),
+}
+
+struct VersionedId {
+    since: Ident,
+    until: Option<Ident>,
+    id: LitInt,
+}
+
+impl Parse for VersionedId {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let since = input.parse()?;
+        let until = match input.parse::<Option<Token![..]>>()? {
+            Some(_) => Some(input.parse()?),
+            None => None,
+        };
+        input.parse::<Token![=]>()?;
+        let id = input.parse()?;
+
+        Ok(Self { since, until, id })
+    }
+}
+
+impl Parse for PacketIds {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if !input.peek(syn::token::Bracket) {
+            return Ok(Self::Fixed(input.parse()?));
+        }
+
+        let content;
+        bracketed!(content in input);
+
+        let ids = content.parse_terminated::<VersionedId, Token![,]>(VersionedId::parse)?;
+
+        if ids.is_empty() {
+            return Err(content.error("Expected at least one packet id!"));
+        }
+
+        Ok(Self::Versioned(ids.into_iter().collect()))
+    }
+}
+
+struct PacketAttribute {
+    ids: PacketIds,
+    direction: Ident,
+    state: Ident,
+    packet: Ident,
+}
+
+// #[packet(0x00, Clientbound, Status, StatusResponse)]
+impl Parse for PacketAttribute {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ids = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let direction = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let state = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let packet = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+
+        Ok(Self {
+            ids,
+            direction,
+            state,
+            packet,
+        })
+    }
+}
+
+// an id together with the protocol versions it is used in, since is inclusive and until is exclusive.
+#[derive(Clone)]
+struct IdRange {
+    id: i32,
+    literal: LitInt,
+    since: Option<(Ident, i32)>,
+    until: Option<(Ident, i32)>,
+}
+
+impl IdRange {
+    fn overlaps(&self, other: &IdRange) -> bool {
+        let start = |range: &IdRange| range.since.as_ref().map_or(i32::MIN, |since| since.1);
+        let end = |range: &IdRange| range.until.as_ref().map_or(i32::MAX, |until| until.1);
+
+        start(self) < end(other) && start(other) < end(self)
+    }
+
+    fn condition(&self) -> Option<proc_macro2::TokenStream> {
+        let since = self.since.as_ref().map(|(since, _)| {
+            quote! { version >= types::packet::ProtocolVersion::#since }
+        });
+        let until = self.until.as_ref().map(|(until, _)| {
+            quote! { version < types::packet::ProtocolVersion::#until }
+        });
+
+        match (since, until) {
+            (Some(since), Some(until)) => Some(quote! { #since && #until }),
+            (Some(condition), None) | (None, Some(condition)) => Some(condition),
+            (None, None) => None,
+        }
+    }
+}
+
+fn parse_version(ident: &Ident) -> syn::Result<(Ident, i32)> {
+    match ident.to_string().parse::<ProtocolVersion>() {
+        Ok(version) => Ok((ident.clone(), version.0)),
+        Err(error) => Err(syn::Error::new(ident.span(), error)),
+    }
+}
+
+fn id_ranges(ids: &PacketIds) -> syn::Result<Vec<IdRange>> {
+    let ids = match ids {
+        PacketIds::Fixed(id) => {
+            return Ok(vec![IdRange {
+                id: id.base10_parse()?,
+                literal: id.clone(),
+                since: None,
+                until: None,
+            }])
+        }
+        PacketIds::Versioned(ids) => ids,
+    };
+
+    let mut ranges = Vec::new();
+
+    for (index, entry) in ids.iter().enumerate() {
+        let since = parse_version(&entry.since)?;
+        let next = ids
+            .get(index + 1)
+            .map(|next| parse_version(&next.since))
+            .transpose()?;
+
+        if let Some(next) = &next {
+            if next.1 <= since.1 {
+                return Err(syn::Error::new(
+                    next.0.span(),
+                    "Packet ids have to be listed from the oldest to the newest version!",
+                ));
+            }
+        }
+
+        let until = match &entry.until {
+            Some(until) => {
+                let until = parse_version(until)?;
+
+                if until.1 <= since.1 || next.as_ref().is_some_and(|next| until.1 > next.1) {
+                    return Err(syn::Error::new(
+                        until.0.span(),
+                        "The end of a packet id has to be between its start and the next entry!",
+                    ));
+                }
+
+                Some(until)
+            }
+            None => next,
+        };
+
+        ranges.push(IdRange {
+            id: entry.id.base10_parse()?,
+            literal: entry.id.clone(),
+            since: Some(since),
+            until,
+        });
+    }
+
+    Ok(ranges)
+}
+
 #[proc_macro_derive(ParsePacket, attributes(packet))]
 pub fn define_packet_parsers(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = input.ident;
 
-    if let Data::Enum(data) = &input.data {
-        let mut ids = quote! {};
-        let mut id_match_map = HashMap::<
-            ClientState,
-            HashMap<PacketDirection, Vec<PacketMacroData<proc_macro2::TokenStream>>>,
-        >::new();
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return syn::Error::new(name.span(), "ParsePacket is only allowed on enums!")
+                .to_compile_error()
+                .into()
+        }
+    };
+
+    let mut tokens = quote! {};
+    let mut ids = quote! {};
+    let mut packet_variants = quote! {};
+    let mut decode_variants = quote! {};
+    let mut encode_variants = quote! {};
+    let mut packet_impls = quote! {};
+    let mut id_match_map = HashMap::<
+        ClientState,
+        HashMap<PacketDirection, Vec<PacketMacroData<proc_macro2::TokenStream>>>,
+    >::new();
+
+    let mut used_ids = HashMap::<(ClientState, PacketDirection, i32), Vec<(IdRange, Ident)>>::new();
+    let mut errors: Option<syn::Error> = None;
+
+    let mut push_error = |error: syn::Error| match &mut errors {
+        Some(errors) => errors.combine(error),
+        None => errors = Some(error),
+    };
 
-        let tokens: proc_macro2::TokenStream = data
-            .variants
+    for variant in &data.variants {
+        let variant_name = &variant.ident;
+
+        let attribute = match variant
+            .attrs
             .iter()
-            .map(|variant| {
-                let attributes = &variant.attrs;
-                let variant_name = &variant.ident;
-
-                // we have to write a better parser for this, this sucks. but i can't be arsed to spend more time on this right now.
-                let packet_value = attributes
-                    .iter()
-                    .next()
-                    .unwrap()
-                    .tokens
-                    .to_string()
-                    .replace("(", "")
-                    .replace(")", "");
-
-                let split = packet_value.split(", ").collect::<Vec<&str>>();
-
-                let id = split[0].parse::<proc_macro2::TokenStream>().unwrap();
-                let packet_direction = split[1]
-                    .parse::<PacketDirection>()
-                    .expect("PacketDirection was not able to be parsed from type!");
-
-                let required_client_state = split[2]
-                    .parse::<ClientState>()
-                    .expect("ClientState was not able to be parsed from type!");
-
-                let packet_value = split[3].parse::<proc_macro2::TokenStream>().unwrap();
-
-                let packet_value_snake = to_snake_case(&packet_value.to_string())
-                    .parse::<proc_macro2::TokenStream>()
-                    .unwrap();
-
-                if !id_match_map.contains_key(&required_client_state) {
-                    id_match_map.insert(required_client_state, HashMap::new());
-                }
+            .find(|attribute| attribute.path.is_ident("packet"))
+        {
+            Some(attribute) => attribute,
+            None => {
+                push_error(syn::Error::new(
+                    variant_name.span(),
+                    "Missing #[packet(id, direction, state, packet)] attribute!",
+                ));
+                continue;
+            }
+        };
 
-                let map = id_match_map.get_mut(&required_client_state).expect("wtf");
+        let attribute = match attribute.parse_args::<PacketAttribute>() {
+            Ok(attribute) => attribute,
+            Err(error) => {
+                push_error(error);
+                continue;
+            }
+        };
 
-                if !map.contains_key(&packet_direction) {
-                    map.insert(packet_direction, Vec::new());
-                }
+        let ranges = match id_ranges(&attribute.ids) {
+            Ok(ranges) => ranges,
+            Err(error) => {
+                push_error(error);
+                continue;
+            }
+        };
 
-                let vec = map.get_mut(&packet_direction).expect("wha");
+        let packet_direction = match attribute.direction.to_string().parse::<PacketDirection>() {
+            Ok(direction) => direction,
+            Err(error) => {
+                push_error(syn::Error::new(attribute.direction.span(), error));
+                continue;
+            }
+        };
 
-                let data = PacketMacroData::<proc_macro2::TokenStream> {
-                    variant: variant_name.to_token_stream(),
-                    id: id.clone(),
-                    packet: packet_value.clone(),
-                };
+        let required_client_state = match attribute.state.to_string().parse::<ClientState>() {
+            Ok(state) => state,
+            Err(error) => {
+                push_error(syn::Error::new(attribute.state.span(), error));
+                continue;
+            }
+        };
 
-                vec.push(data);
+        let packet_value = &attribute.packet;
+
+        // two packets sharing an id in the same state, direction and protocol version can never be told apart.
+        let mut has_duplicate = false;
+
+        for range in &ranges {
+            let used = used_ids
+                .entry((required_client_state, packet_direction, range.id))
+                .or_default();
+
+            if let Some((_, existing)) = used.iter().find(|(other, _)| other.overlaps(range)) {
+                push_error(syn::Error::new(
+                    range.literal.span(),
+                    format!(
+                        "Packet id {:#04x} is already used by {} in {} {}!",
+                        range.id, existing, packet_direction, required_client_state
+                    ),
+                ));
+                has_duplicate = true;
+            }
 
-                ids.extend(quote! {
-                    #name::#variant_name => (#id as u8),
-                });
+            used.push((range.clone(), packet_value.clone()));
+        }
 
-                quote! {
-                    fn #packet_value_snake<R: Read>(
-                        reader: &mut R
-                    ) -> Result<#packet_value, std::io::Error> {
-                        #packet_value::decode(reader)
-                    }
+        if has_duplicate {
+            continue;
+        }
+
+        let packet_value_snake = to_snake_case(&packet_value.to_string())
+            .parse::<proc_macro2::TokenStream>()
+            .unwrap();
+
+        let mut id_for_expand = quote! {};
+
+        for range in &ranges {
+            let id = proc_macro2::Literal::i32_unsuffixed(range.id);
+            let pattern = match range.condition() {
+                Some(condition) => {
+                    id_for_expand.extend(quote! {
+                        if #condition {
+                            return Some(#id);
+                        }
+                    });
+
+                    quote! { #id if #condition }
                 }
-            })
-            .collect();
+                None => quote! { #id },
+            };
+
+            id_match_map
+                .entry(required_client_state)
+                .or_default()
+                .entry(packet_direction)
+                .or_default()
+                .push(PacketMacroData::<proc_macro2::TokenStream> {
+                    variant: variant_name.to_token_stream(),
+                    id: pattern,
+                    packet: packet_value.to_token_stream(),
+                });
+        }
+
+        // the newest id, the ranges are sorted by version.
+        let id = proc_macro2::Literal::i32_unsuffixed(ranges.last().unwrap().id);
+
+        ids.extend(quote! {
+            #name::#variant_name => #id,
+        });
+
+        packet_variants.extend(quote! {
+            #packet_value(#packet_value),
+        });
+
+        decode_variants.extend(quote! {
+            #name::#variant_name => Packet::#packet_value(Self::#packet_value_snake(reader, version)?),
+        });
+
+        encode_variants.extend(quote! {
+            Packet::#packet_value(packet) => packet.encode_packet_versioned(writer, version),
+        });
 
-        let mut id_match_expanded = quote! {};
-        let invalid_state_error = quote! {
-            Err(std::io::Error::new(std::io::ErrorKind::NotFound, "Unimplemented or invalid packet id."))?
+        let packet_direction_value = &attribute.direction;
+        let required_client_state_value = &attribute.state;
+
+        let id_for = if matches!(attribute.ids, PacketIds::Versioned(_)) {
+            quote! {
+                fn id_for(version: types::packet::ProtocolVersion) -> Option<i32> {
+                    #id_for_expand
+                    None
+                }
+            }
+        } else {
+            quote! {}
         };
 
-        let ids_name = "id".parse::<proc_macro2::TokenStream>().unwrap();
-        let states_name = "state".parse::<proc_macro2::TokenStream>().unwrap();
-        let directions_name = "direction".parse::<proc_macro2::TokenStream>().unwrap();
+        packet_impls.extend(quote! {
+            impl crate::packets::registry::Packet for #packet_value {
+                const ID: i32 = #id;
+                const STATE: ClientState = ClientState::#required_client_state_value;
+                const DIRECTION: PacketDirection = PacketDirection::#packet_direction_value;
+
+                #id_for
+            }
+        });
+
+        tokens.extend(quote! {
+            fn #packet_value_snake<R: Read>(
+                reader: &mut R,
+                version: types::packet::ProtocolVersion
+            ) -> Result<#packet_value, std::io::Error> {
+                <#packet_value as crate::packets::VersionedDecodable>::decode_versioned(reader, version)
+            }
+        });
+    }
+
+    if let Some(errors) = errors {
+        return errors.to_compile_error().into();
+    }
 
-        for value in id_match_map {
-            let client_state = value
+    let mut id_match_expanded = quote! {};
+    let invalid_state_error = quote! {
+        Err(std::io::Error::new(std::io::ErrorKind::NotFound, "Unimplemented or invalid packet id."))?
+    };
+
+    let ids_name = "id".parse::<proc_macro2::TokenStream>().unwrap();
+    let states_name = "state".parse::<proc_macro2::TokenStream>().unwrap();
+    let directions_name = "direction".parse::<proc_macro2::TokenStream>().unwrap();
+
+    for value in id_match_map {
+        let client_state = value
+            .0
+            .to_string()
+            .parse::<proc_macro2::TokenStream>()
+            .unwrap();
+        let map = value.1;
+        let mut directions = quote! {};
+
+        for value in map {
+            let direction = value
                 .0
                 .to_string()
                 .parse::<proc_macro2::TokenStream>()
                 .unwrap();
-            let map = value.1;
-            let mut directions = quote! {};
-
-            for value in map {
-                let direction = value
-                    .0
-                    .to_string()
-                    .parse::<proc_macro2::TokenStream>()
-                    .unwrap();
-                let data = value.1;
-                let mut packet_ids = quote! {};
-
-                for data in data {
-                    let id = data.id;
-                    // let packet_value = data.packet;
-                    let variant_name = data.variant;
-
-                    packet_ids.extend(quote! {
-                        #id => Self::#variant_name,
-                    });
-                }
+            let data = value.1;
+            let mut packet_ids = quote! {};
 
-                directions.extend(quote! {
-                    PacketDirection::#direction => {
-                        match #ids_name {
-                            #packet_ids
-                            _ => #invalid_state_error
-                        }
-                    },
-                })
+            for data in data {
+                let id = data.id;
+                let variant_name = data.variant;
+
+                packet_ids.extend(quote! {
+                    #id => Self::#variant_name,
+                });
             }
 
-            id_match_expanded.extend(quote! {
-                ClientState::#client_state => {
-                    match #directions_name {
-                        #directions
+            directions.extend(quote! {
+                PacketDirection::#direction => {
+                    match #ids_name {
+                        #packet_ids
                         _ => #invalid_state_error
                     }
                 },
-            });
+            })
         }
 
-        quote! {
-            impl #name {
-                #tokens
-
-                fn get_id(
-                    &self
-                ) -> u8 {
-                    match self {
-                        #ids
-                    }
+        id_match_expanded.extend(quote! {
+            ClientState::#client_state => {
+                match #directions_name {
+                    #directions
+                    _ => #invalid_state_error
                 }
+            },
+        });
+    }
 
-                fn get_from_id(
-                    #ids_name: u8,
-                    #states_name: ClientState,
-                    #directions_name: PacketDirection
-                ) -> Result<Self, std::io::Error> {
-                    Ok(match #states_name {
-                        #id_match_expanded
-                        _ => #invalid_state_error
-                    })
+    quote! {
+        #[derive(Debug, PartialEq)]
+        pub enum Packet {
+            #packet_variants
+        }
+
+        #packet_impls
+
+        // encoding through the registry prefixes every packet with its id.
+        impl crate::packets::Encodable for Packet {
+            fn encode<W: std::io::Write>(
+                &self,
+                writer: &mut W
+            ) -> Result<(), std::io::Error> {
+                crate::packets::VersionedEncodable::encode_versioned(
+                    self,
+                    writer,
+                    types::packet::ProtocolVersion::LATEST,
+                )
+            }
+        }
+
+        impl crate::packets::VersionedEncodable for Packet {
+            fn encode_versioned<W: std::io::Write>(
+                &self,
+                writer: &mut W,
+                version: types::packet::ProtocolVersion
+            ) -> Result<(), std::io::Error> {
+                use crate::packets::registry::Packet as _;
+
+                match self {
+                    #encode_variants
                 }
             }
         }
-        .into()
-    } else {
-        panic!("Only allowed on enums!");
+
+        impl #name {
+            #tokens
+
+            fn decode<R: Read>(
+                &self,
+                reader: &mut R,
+                version: types::packet::ProtocolVersion
+            ) -> Result<Packet, std::io::Error> {
+                Ok(match self {
+                    #decode_variants
+                })
+            }
+
+            fn get_id(
+                &self
+            ) -> i32 {
+                match self {
+                    #ids
+                }
+            }
+
+            fn get_from_id(
+                #ids_name: i32,
+                #states_name: ClientState,
+                #directions_name: PacketDirection,
+                version: types::packet::ProtocolVersion
+            ) -> Result<Self, std::io::Error> {
+                Ok(match #states_name {
+                    #id_match_expanded
+                    _ => #invalid_state_error
+                })
+            }
+        }
     }
+    .into()
 }
 
 // we can definitely otpimize this, i just cannot be arsed to do the regex stuff.
@@ -275,3 +778,77 @@ fn to_snake_case(s: &str) -> String {
 
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(ids: &str) -> syn::Result<Vec<IdRange>> {
+        id_ranges(&syn::parse_str(ids)?)
+    }
+
+    fn versions(ranges: &[IdRange]) -> Vec<(i32, Option<i32>, Option<i32>)> {
+        ranges
+            .iter()
+            .map(|range| {
+                (
+                    range.id,
+                    range.since.as_ref().map(|since| since.1),
+                    range.until.as_ref().map(|until| until.1),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ids_are_used_until_the_next_entry() {
+        let fixed = ranges("0x01").unwrap();
+        assert_eq!(versions(&fixed), [(0x01, None, None)]);
+        assert!(fixed[0].condition().is_none());
+
+        let versioned =
+            ranges("[V1_19_3 = 0x03, V1_19_4 = 0x04, V1_20_2..V1_21_2 = 0x03]").unwrap();
+        assert_eq!(
+            versions(&versioned),
+            [
+                (0x03, Some(761), Some(762)),
+                (0x04, Some(762), Some(764)),
+                (0x03, Some(764), Some(768)),
+            ]
+        );
+        assert_eq!(
+            versioned[1].condition().unwrap().to_string(),
+            quote! {
+                version >= types::packet::ProtocolVersion::V1_19_4
+                    && version < types::packet::ProtocolVersion::V1_20_2
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn rejects_misordered_ids() {
+        assert!(ranges("[]").is_err());
+        assert!(ranges("[V1_20 = 0x01, V1_19_3 = 0x02]").is_err());
+        assert!(ranges("[V1_20 = 0x01, V1_20 = 0x02]").is_err());
+        assert!(ranges("[V1_20..V1_19_3 = 0x01]").is_err());
+        assert!(ranges("[V1_19_3..V1_20_2 = 0x01, V1_20 = 0x02]").is_err());
+        assert!(ranges("[V1_0 = 0x01]").is_err());
+    }
+
+    #[test]
+    fn overlapping_ids_are_duplicates() {
+        let spawn_player = ranges("[V1_19_3 = 0x02, V1_19_4..V1_20_2 = 0x03]").unwrap();
+        let animation = ranges("[V1_19_3 = 0x03, V1_19_4 = 0x04, V1_20_2 = 0x03]").unwrap();
+        let fixed = ranges("0x03").unwrap();
+
+        // both use 0x03, but never in the same version.
+        assert!(!spawn_player[1].overlaps(&animation[0]));
+        assert!(!spawn_player[1].overlaps(&animation[2]));
+        assert!(!animation[0].overlaps(&animation[2]));
+
+        assert!(fixed[0].overlaps(&spawn_player[1]));
+        assert!(animation[2].overlaps(&ranges("[V1_21 = 0x03]").unwrap()[0]));
+        assert!(spawn_player[1].overlaps(&ranges("[V1_20 = 0x03]").unwrap()[0]));
+    }
+}
@@ -1,11 +1,13 @@
 pub mod packet {
     use std::{fmt::Display, str::FromStr};
 
-    #[derive(Hash, PartialEq, Eq, Clone, Copy)]
+    #[derive(Hash, PartialEq, Eq, Clone, Copy, Debug)]
     pub enum ClientState {
         Status,
         Handshake,
         Login,
+        // since 1.20.2, between Login and Play. the server sends registries and resource packs here.
+        Configuration,
         Play,
     }
 
@@ -17,6 +19,7 @@ pub mod packet {
                 "Status" => Self::Status,
                 "Handshake" => Self::Handshake,
                 "Login" => Self::Login,
+                "Configuration" => Self::Configuration,
                 "Play" => Self::Play,
                 _ => Err(std::io::Error::new(
                     std::io::ErrorKind::Unsupported,
@@ -32,12 +35,13 @@ pub mod packet {
                 Self::Status => write!(f, "Status"),
                 Self::Handshake => write!(f, "Handshake"),
                 Self::Login => write!(f, "Login"),
+                Self::Configuration => write!(f, "Configuration"),
                 Self::Play => write!(f, "Play"),
             }
         }
     }
 
-    #[derive(Hash, PartialEq, Eq, Clone, Copy)]
+    #[derive(Hash, PartialEq, Eq, Clone, Copy, Debug)]
     pub enum PacketDirection {
         Clientbound,
         Serverbound,
@@ -67,6 +71,50 @@ pub mod packet {
         }
     }
 
+    // the protocol version number sent in the handshake. only the versions the crate knows about have a name.
+    #[derive(Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+    pub struct ProtocolVersion(pub i32);
+
+    impl ProtocolVersion {
+        pub const V1_19: Self = Self(759);
+        pub const V1_19_1: Self = Self(760);
+        pub const V1_19_3: Self = Self(761);
+        pub const V1_19_4: Self = Self(762);
+        pub const V1_20: Self = Self(763);
+        pub const V1_20_2: Self = Self(764);
+        pub const V1_20_3: Self = Self(765);
+        pub const V1_20_5: Self = Self(766);
+        pub const V1_21: Self = Self(767);
+        pub const V1_21_2: Self = Self(768);
+
+        pub const LATEST: Self = Self::V1_21;
+    }
+
+    // the names of the constants above, used by the packet registry macro.
+    impl FromStr for ProtocolVersion {
+        type Err = std::io::Error;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(match s {
+                "V1_19" => Self::V1_19,
+                "V1_19_1" => Self::V1_19_1,
+                "V1_19_3" => Self::V1_19_3,
+                "V1_19_4" => Self::V1_19_4,
+                "V1_20" => Self::V1_20,
+                "V1_20_2" => Self::V1_20_2,
+                "V1_20_3" => Self::V1_20_3,
+                "V1_20_5" => Self::V1_20_5,
+                "V1_21" => Self::V1_21,
+                "V1_21_2" => Self::V1_21_2,
+                "LATEST" => Self::LATEST,
+                _ => Err(std::io::Error::new(
+                    std::io::ErrorKind::Unsupported,
+                    format!("Unable to find ProtocolVersion with name {}", s),
+                ))?,
+            })
+        }
+    }
+
     #[derive(Hash, PartialEq, Eq)]
     pub struct PacketMacroData<T> {
         pub variant: T,